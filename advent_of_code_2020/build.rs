@@ -0,0 +1,109 @@
+//! Generates a test for every part of every example that has expected answers.
+//!
+//! An example `src/dayN/data/NAME.txt` is tested if there is a
+//! `src/dayN/data/NAME.expected` next to it, with one line "part answer" per
+//! part that should be checked, e.g.
+//!
+//! ```text
+//! # part answer
+//! 1 35
+//! 2 8
+//! ```
+//!
+//! Line breaks in answers are written as `\n`. The tests end up in
+//! `tests/examples.rs`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Example {
+    day: u8,
+    name: String,
+    input: PathBuf,
+    answers: Vec<(u8, String)>,
+}
+
+fn parse_expected(path: &Path) -> Vec<(u8, String)> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Unable to read '{}': {}", path.display(), e));
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let mut parts = l.splitn(2, ' ');
+            let part = parts.next()
+                .and_then(|p| p.parse::<u8>().ok())
+                .unwrap_or_else(|| panic!("'{}' in '{}' doesn't start with a part", l, path.display()));
+            let answer = parts.next().unwrap_or("").replace("\\n", "\n");
+            (part, answer)
+        })
+        .collect()
+}
+
+fn find_examples(src: &Path) -> Vec<Example> {
+    let mut examples = vec![];
+
+    for entry in fs::read_dir(src).expect("Unable to read src") {
+        let dir = entry.expect("Unable to read src").path();
+        let day = match dir.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_prefix("day")) {
+            Some(day) => match day.parse::<u8>() {
+                Ok(day) => day,
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+
+        let data = dir.join("data");
+        if !data.is_dir() {
+            continue;
+        }
+
+        for file in fs::read_dir(&data).expect("Unable to read data") {
+            let expected = file.expect("Unable to read data").path();
+            if expected.extension().and_then(|e| e.to_str()) != Some("expected") {
+                continue;
+            }
+
+            let input = expected.with_extension("txt");
+            if !input.exists() {
+                panic!("'{}' has no input '{}'", expected.display(), input.display());
+            }
+
+            examples.push(Example {
+                day,
+                name: expected.file_stem().unwrap().to_string_lossy().to_string(),
+                input,
+                answers: parse_expected(&expected),
+            });
+        }
+    }
+
+    examples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    examples
+}
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let src = root.join("src");
+    println!("cargo:rerun-if-changed=src");
+
+    let mut tests = String::new();
+    for example in find_examples(&src) {
+        let name = example.name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect::<String>();
+
+        for (part, answer) in &example.answers {
+            tests.push_str(&format!(
+                "#[test]\nfn day{}_{}_part{}() {{\n    check({}, {}, include_str!({:?}), {:?});\n}}\n\n",
+                example.day, name, part, example.day, part, example.input.display().to_string(), answer,
+            ));
+        }
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(&out, tests).expect("Unable to write the example tests");
+}
@@ -1,7 +1,10 @@
 use itertools::Itertools;
+use super::utils::{parse, parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
-pub fn fold_2(v: &Vec<i32>) -> Option<i32> {
-    for c in v.into_iter().combinations(2) {
+pub fn fold_2(v: &[i32]) -> Option<i32> {
+    for c in v.iter().combinations(2) {
         if c[0] + c[1] == 2020 {
             return Some(c[0] * c[1]);
         }
@@ -10,8 +13,8 @@ pub fn fold_2(v: &Vec<i32>) -> Option<i32> {
     None
 }
 
-pub fn fold_3(v: &Vec<i32>) -> Option<i32> {
-    for c in v.into_iter().combinations(3) {
+pub fn fold_3(v: &[i32]) -> Option<i32> {
+    for c in v.iter().combinations(3) {
         if c[0] + c[1] + c[2] == 2020 {
             return Some(c[0] * c[1] * c[2]);
         }
@@ -20,34 +23,33 @@ pub fn fold_3(v: &Vec<i32>) -> Option<i32> {
     None
 }
 
-fn parse_input() -> Vec<i32> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| v.parse::<i32>().unwrap())
-        .collect::<Vec<_>>()
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(input, parse::<i32>)
 }
 
-pub fn problem1() {
-    let input = parse_input();
-    let solution = fold_2(&input);
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
 
-    if let Some(result) = solution {
-        println!("1/1: {}", result);
-    } else {
-        println!("Found nothing.");
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let solution = fold_2(input)
+            .ok_or(Error::solve("Found nothing."))?;
+
+        Ok(Answer::from(solution))
     }
-}
 
-pub fn problem2() {
-    let input = parse_input();
-    let solution = fold_3(&input);
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let solution = fold_3(input)
+            .ok_or(Error::solve("Found nothing."))?;
 
-    if let Some(result) = solution {
-        println!("1/2: {}", result);
-    } else {
-        println!("Found nothing.");
+        Ok(Answer::from(solution))
     }
 }
 
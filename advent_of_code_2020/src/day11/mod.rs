@@ -1,110 +1,62 @@
-use std::collections::HashMap;
-use super::utils::ParseError;
-
-fn parse_input() -> Vec<Vec<char>> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| v.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>()
+use super::cancel;
+use super::frames::{self, Frame};
+use super::progress;
+use super::utils::{Error, Input, ParseError};
+use super::utils::grid::{Grid, Neighborhood, Pos};
+use super::answer::Answer;
+use super::solution::Solution;
+
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<World, ParseError> {
+    Input::new(input).grid()
 }
 
-type Coords = (i32, i32);
-type World = HashMap<Coords, char>;
+pub type World = Grid<char>;
 
-fn map_size(input: &Vec<Vec<char>>) -> Result<Coords, ParseError> {
-    let height = input.len();
-    let width = input.last().ok_or(ParseError::new("Empty map."))?.len();
-
-    Ok((height as i32, width as i32))
-}
-
-fn generate_world(input: Vec<Vec<char>>) -> World {
-    let mut map = HashMap::new();
-
-    input.into_iter()
-        .enumerate()
-        .for_each(|(row, line)| {
-            line.into_iter()
-                .enumerate()
-                .for_each(|(col, char)| {
-                    map.entry((row as i32, col as i32)).or_insert(char);
-                })
-        });
-
-    map
-}
-
-fn count_occupied_neighbors(map: &World, coords: &Coords) -> usize {
-    let mut count = 0;
-
-    for i in -1..=1 {
-        for j in -1..=1 {
-            if i == 0 && j == 0 {
-                continue;
-            }
-
-            if let Some(status) = map.get(&(coords.0 + i, coords.1 + j)) {
-                if status == &'#' {
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    count
+pub fn count_occupied_neighbors(map: &World, coords: Pos) -> usize {
+    map.neighbors(coords, Neighborhood::Eight)
+        .filter(|n| map[*n] == '#')
+        .count()
 }
 
-fn tick<F>(map: World, neighbor_count_strategy: F, neighbor_threshold: usize) -> World
-    where F: Fn(&World, &Coords) -> usize {
+pub fn tick<F>(map: &World, neighbor_count_strategy: F, neighbor_threshold: usize) -> World
+    where F: Fn(&World, Pos) -> usize {
 
-    let mut new_world = HashMap::new();
-
-    for (coords, status) in &map {
-        let occupied_neighbors = neighbor_count_strategy(&map, &coords);
+    map.map(|coords, status| {
+        let occupied_neighbors = neighbor_count_strategy(map, coords);
 
         if status == &'L' && occupied_neighbors == 0 {
-            new_world.entry(*coords).or_insert('#');
+            '#'
         } else if status == &'#' && occupied_neighbors >= neighbor_threshold {
-            new_world.entry(*coords).or_insert('L');
+            'L'
         } else {
-            new_world.entry(*coords).or_insert(*status);
+            *status
         }
-    }
-
-    new_world
+    })
 }
 
-fn count_occupied_seats(map: &World) -> usize {
-    map.iter()
-        .filter(|(_, &v)| v == '#')
-        .count()
+pub fn count_occupied_seats(map: &World) -> usize {
+    map.count(|v| *v == '#')
 }
 
-fn print_world(world: &World, size: &Coords) {
-    for row in 0..size.0 {
-        for col in 0..size.1 {
-            print!("{}", world.get(&(row, col)).unwrap());
-        }
-        println!("");
-    }
-    println!("");
-}
-
-pub fn run<F>(neighbor_count_strategy: &F, neighbor_threshold: usize) -> Result<usize, ParseError>
-    where F: Fn(&World, &Coords) -> usize {
+pub fn run<F>(input: &World, neighbor_count_strategy: &F, neighbor_threshold: usize) -> Result<usize, Error>
+    where F: Fn(&World, Pos) -> usize {
 
-    let input = parse_input();
-    let _size = map_size(&input)?;
-    let mut old_world = generate_world(input);
+    if input.height() == 0 {
+        return Err(Error::solve("Empty map."));
+    }
+    let mut old_world = input.clone();
 
-    // print_world(&old_world, &size);
+    frames::emit(|| Frame::new("round 0", old_world.clone()))?;
 
     let mut last_count = 0;
-    loop {
-        let new_world = tick(old_world.clone(), neighbor_count_strategy, neighbor_threshold);
-        // print_world(&new_world, &size);
+    let mut task = progress::task("Seating passengers", 0);
+    for round in 1.. {
+        task.inc(1);
+        cancel::checkpoint()?;
+        let new_world = tick(&old_world, neighbor_count_strategy, neighbor_threshold);
+        frames::emit(|| Frame::new(&format!("round {}", round), new_world.clone()))?;
 
         let occupied = count_occupied_seats(&new_world);
         if last_count == occupied {
@@ -117,17 +69,10 @@ pub fn run<F>(neighbor_count_strategy: &F, neighbor_threshold: usize) -> Result<
     Ok(count_occupied_seats(&old_world))
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let result = run(&count_occupied_neighbors, 4)?;
-
-    println!("11/1: # of occupied seats: {}", result);
-    Ok(())
-}
-
-fn trace_occupation(map: &World, coords: &Coords, direction: &Coords) -> bool {
+pub fn trace_occupation(map: &World, coords: Pos, direction: Pos) -> bool {
     let mut coords = (coords.0 + direction.0, coords.1 + direction.1);
 
-    while let Some(status) = map.get(&coords) {
+    while let Some(status) = map.get(coords) {
         if status == &'#' {
             return true;
         }
@@ -143,27 +88,31 @@ fn trace_occupation(map: &World, coords: &Coords, direction: &Coords) -> bool {
     false
 }
 
-fn count_occupied_neighbors_2(world: &World, coords: &Coords) -> usize {
-    let mut count = 0;
+pub fn count_occupied_neighbors_2(world: &World, coords: Pos) -> usize {
+    Neighborhood::Eight.offsets()
+        .iter()
+        .filter(|direction| trace_occupation(world, coords, **direction))
+        .count()
+}
 
-    for i in -1..=1 {
-        for j in -1..=1 {
-            if i == 0 && j == 0 {
-                continue;
-            }
+pub struct Day11;
 
-            if trace_occupation(world, coords, &(i, j)) {
-                count += 1;
-            }
-        }
+impl Solution for Day11 {
+    type Input = World;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
     }
 
-    count
-}
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let result = run(input, &count_occupied_neighbors, 4)?;
+
+        Ok(Answer::from(result))
+    }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let result = run(&count_occupied_neighbors_2, 5)?;
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let result = run(input, &count_occupied_neighbors_2, 5)?;
 
-    println!("11/2: # of occupied seats: {}", result);
-    Ok(())
+        Ok(Answer::from(result))
+    }
 }
@@ -1,15 +1,16 @@
 use std::fmt::Debug;
 use std::str::FromStr;
-use regex::Regex;
-use super::utils::ParseError;
+use super::utils::{parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
 /// Commands steer the ship
 ///
 /// They consist of an operation and a parameter "value".
 #[derive(Debug)]
-struct Command {
-    operation: char,
-    value: i32,
+pub struct Command {
+    pub operation: char,
+    pub value: i32,
 }
 
 /// Parse a Command from the text input
@@ -17,13 +18,7 @@ impl FromStr for Command {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static!{
-            static ref RE: Regex = Regex::new(r"^(\w)(\d+)$").unwrap();
-        }
-
-        let cap = RE.captures(s).unwrap();
-        let operation = cap[1].chars().next().ok_or(ParseError::new(&format!("Unable to parse input: '{}'", s)))?;
-        let value = cap[2].parse::<i32>()?;
+        let (operation, value) = scan!(s, "{1}{}", char, i32)?;
 
         Ok(Command { operation, value })
     }
@@ -44,24 +39,21 @@ impl FromStr for Command {
 ///
 /// Rotation
 /// Implements the L and R commands by modifying the speed.
-trait CommandStrategy {
+pub trait CommandStrategy {
     fn supports_command(&self, command: &Command) -> bool;
 
-    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, ParseError>;
+    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, Error>;
 }
 
-struct CompassDirectMovement {}
+pub struct CompassDirectMovement {}
 
 impl CommandStrategy for CompassDirectMovement {
     fn supports_command(&self, command: &Command) -> bool {
-        match command.operation {
-            'N' | 'E' | 'S' | 'W' => true,
-            _ => false,
-        }
+        matches!(command.operation, 'N' | 'E' | 'S' | 'W')
     }
 
-    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, ParseError> {
-        let mut ship = ship.clone();
+    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, Error> {
+        let mut ship = *ship;
 
         let delta = Position::delta(command.operation, command.value)?;
         ship.position = ship.position.translate(&delta);
@@ -70,18 +62,15 @@ impl CommandStrategy for CompassDirectMovement {
     }
 }
 
-struct CompassSpeedAdjustment {}
+pub struct CompassSpeedAdjustment {}
 
 impl CommandStrategy for CompassSpeedAdjustment {
     fn supports_command(&self, command: &Command) -> bool {
-        match command.operation {
-            'N' | 'E' | 'S' | 'W' => true,
-            _ => false,
-        }
+        matches!(command.operation, 'N' | 'E' | 'S' | 'W')
     }
 
-    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, ParseError> {
-        let mut ship = ship.clone();
+    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, Error> {
+        let mut ship = *ship;
 
         let delta = Position::delta(command.operation, command.value)?;
         ship.speed = ship.speed.translate(&delta);
@@ -89,15 +78,15 @@ impl CommandStrategy for CompassSpeedAdjustment {
         Ok(ship)
     }
 }
-struct ForwardMovement {}
+pub struct ForwardMovement {}
 
 impl CommandStrategy for ForwardMovement {
     fn supports_command(&self, command: &Command) -> bool {
         command.operation == 'F'
     }
 
-    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, ParseError> {
-        let mut ship = ship.clone();
+    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, Error> {
+        let mut ship = *ship;
 
         let delta = ship.speed.scale(command.value);
         ship.position = ship.position.translate(&delta);
@@ -106,7 +95,7 @@ impl CommandStrategy for ForwardMovement {
     }
 }
 
-struct Rotation {}
+pub struct Rotation {}
 
 impl CommandStrategy for Rotation {
     fn supports_command(&self, command: &Command) -> bool {
@@ -114,8 +103,8 @@ impl CommandStrategy for Rotation {
         direction == 'R' || direction == 'L'
     }
 
-    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, ParseError> {
-        let mut ship = ship.clone();
+    fn apply(&self, command: &Command, ship: &Ship) -> Result<Ship, Error> {
+        let mut ship = *ship;
 
         ship.speed = ship.speed.rotate(command.operation, command.value)?;
         Ok(ship)
@@ -125,9 +114,9 @@ impl CommandStrategy for Rotation {
 /// Marks a point on a 2d plane and provides some basic operations on that
 /// point, e.g. scaling, rotation and translation.
 #[derive(Debug, Copy, Clone)]
-struct Position {
-    x: i32,
-    y: i32,
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
 }
 
 impl Position {
@@ -135,20 +124,20 @@ impl Position {
         Self { x, y }
     }
 
-    pub fn delta(direction: char, distance: i32) -> Result<Position, ParseError> {
+    pub fn delta(direction: char, distance: i32) -> Result<Position, Error> {
         let (x, y) = match direction {
             'N' => (0, distance),
             'E' => (distance, 0),
             'S' => (0, -distance),
             'W' => (-distance, 0),
-            e => Err(ParseError::new(&format!("Invalid direction: '{}'", e)))?
+            e => Err(Error::solve(&format!("Invalid direction: '{}'", e)))?
         };
 
         Ok(Position::new(x, y))
     }
 
     pub fn scale(&self, factor: i32) -> Position {
-        let mut position = self.clone();
+        let mut position = *self;
 
         position.x *= factor;
         position.y *= factor;
@@ -156,7 +145,7 @@ impl Position {
         position
     }
 
-    pub fn rotate(&self, direction: char, angle: i32) -> Result<Position, ParseError> {
+    pub fn rotate(&self, direction: char, angle: i32) -> Result<Position, Error> {
         let x = self.x;
         let y = self.y;
 
@@ -169,7 +158,7 @@ impl Position {
             ('R', 180) => (-x, -y),
             ('R', 270) => (-y, x),
 
-            (d, a) => Err(ParseError::new(&format!("Invalid rotation direction ('{}') or angle '{}'", d, a)))?,
+            (d, a) => Err(Error::solve(&format!("Invalid rotation direction ('{}') or angle '{}'", d, a)))?,
         };
 
         Ok(Position::new(new_x, new_y))
@@ -185,9 +174,9 @@ impl Position {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Ship {
-    position: Position,
-    speed: Position,
+pub struct Ship {
+    pub position: Position,
+    pub speed: Position,
 }
 
 impl Ship {
@@ -201,66 +190,64 @@ impl Ship {
 
 /// The navigator holds the implementation for the commands and applies them to
 /// the ship based on the input.
-struct Navigator {
-    strategies: Vec<Box<dyn CommandStrategy>>,
+pub struct Navigator {
+    pub strategies: Vec<Box<dyn CommandStrategy>>,
 }
 
 impl Navigator {
-    fn apply_command(&self, ship: Ship, command: &Command) -> Result<Ship, ParseError> {
+    pub fn apply_command(&self, ship: Ship, command: &Command) -> Result<Ship, Error> {
         let strategy = self.strategies.iter()
-            .filter(|s| s.supports_command(command))
-            .next()
-            .ok_or(ParseError::new(&format!("Could not find strategy for command '{:?}'", command)))?;
+            .find(|s| s.supports_command(command))
+            .ok_or(Error::solve(&format!("Could not find strategy for command '{:?}'", command)))?;
 
         strategy.apply(command, &ship)
     }
 }
 
-fn parse_input() -> Result<Vec<Command>, ParseError> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| Command::from_str(v))
-        .collect::<Result<Vec<_>, ParseError>>()
-}
+pub const INPUT: &str = include_str!("./data/input.txt");
 
-pub fn problem1() -> Result<(), ParseError> {
-    let strategies: Vec<Box<dyn CommandStrategy>> = vec![
-        Box::new(CompassDirectMovement {}),
-        Box::new(ForwardMovement {}),
-        Box::new(Rotation {})
-    ];
-    let navigator = Navigator { strategies };
-    let initial_speed = Position::new(1, 0);
-    let ship = Ship::init(initial_speed);
+pub fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_lines(input, Command::from_str)
+}
 
-    let commands = parse_input()?;
+pub struct Day12;
 
-    let destination = commands.into_iter()
-        .try_fold(ship, |ship, command| navigator.apply_command(ship, &command))?;
+impl Solution for Day12 {
+    type Input = Vec<Command>;
 
-    println!("12/1: manhattan distance: {}", destination.position.manhattan());
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    Ok(())
-}
+    fn part1(commands: &Self::Input) -> Result<Answer, Error> {
+        let strategies: Vec<Box<dyn CommandStrategy>> = vec![
+            Box::new(CompassDirectMovement {}),
+            Box::new(ForwardMovement {}),
+            Box::new(Rotation {})
+        ];
+        let navigator = Navigator { strategies };
+        let initial_speed = Position::new(1, 0);
+        let ship = Ship::init(initial_speed);
 
-pub fn problem2() -> Result<(), ParseError> {
-    let strategies: Vec<Box<dyn CommandStrategy>> = vec![
-        Box::new(CompassSpeedAdjustment {}),
-        Box::new(ForwardMovement {}),
-        Box::new(Rotation {})
-    ];
-    let navigator = Navigator { strategies };
-    let initial_speed = Position::new(10, 1);
-    let ship = Ship::init(initial_speed);
+        let destination = commands.iter()
+            .try_fold(ship, |ship, command| navigator.apply_command(ship, command))?;
 
-    let commands = parse_input()?;
+        Ok(Answer::from(destination.position.manhattan()))
+    }
 
-    let destination = commands.into_iter()
-        .try_fold(ship, |ship, command| navigator.apply_command(ship, &command))?;
+    fn part2(commands: &Self::Input) -> Result<Answer, Error> {
+        let strategies: Vec<Box<dyn CommandStrategy>> = vec![
+            Box::new(CompassSpeedAdjustment {}),
+            Box::new(ForwardMovement {}),
+            Box::new(Rotation {})
+        ];
+        let navigator = Navigator { strategies };
+        let initial_speed = Position::new(10, 1);
+        let ship = Ship::init(initial_speed);
 
-    println!("12/2: manhattan distance: {}", destination.position.manhattan());
+        let destination = commands.iter()
+            .try_fold(ship, |ship, command| navigator.apply_command(ship, command))?;
 
-    Ok(())
+        Ok(Answer::from(destination.position.manhattan()))
+    }
 }
@@ -1,25 +1,22 @@
 use std::collections::{HashMap, HashSet};
-use regex::Regex;
-use super::utils::ParseError;
+use super::utils::{parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     Mask((u64, u64)),
     Write((usize, u64)),
 }
 
-fn parse_command(s: &str) -> Result<Command, ParseError> {
-    lazy_static!{
-        static ref RE_MASK: Regex = Regex::new(r"^mask = ([01X]+)$").unwrap();
-        static ref RE_WRITE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
-    }
-
-    let is_mask = RE_MASK.is_match(s);
-
-    if is_mask {
-        let cap = RE_MASK.captures(s).ok_or(ParseError::new(&format!("Could not parse '{}' as mask", s)))?;
+pub fn parse_command(s: &str) -> Result<Command, ParseError> {
+    if s.starts_with("mask") {
+        let (bits,) = scan!(s, "mask = {}", &str)?;
+        if let Some(i) = bits.find(|c| !"01X".contains(c)) {
+            return Err(ParseError::new("Expected a mask of '0', '1' and 'X'").with_text(&bits[i..i + 1]));
+        }
 
-        let mask: String = cap[1].chars()
+        let mask: String = bits.chars()
             .map(|v| if v == 'X' {
                 '1'
             } else {
@@ -27,7 +24,7 @@ fn parse_command(s: &str) -> Result<Command, ParseError> {
             })
             .collect();
 
-        let overwrite: String = cap[1].chars()
+        let overwrite: String = bits.chars()
             .map(|v| if v == '1' {
                 '1'
             } else {
@@ -39,62 +36,23 @@ fn parse_command(s: &str) -> Result<Command, ParseError> {
         let overwrite = u64::from_str_radix(&overwrite, 2)?;
         Ok(Command::Mask((mask, overwrite)))
     } else {
-        let cap = RE_WRITE.captures(s).ok_or(ParseError::new(&format!("Could not parse '{}' as write", s)))?;
-        let address = cap[1].parse::<usize>()?;
-        let value = cap[2].parse::<u64>()?;
+        let (address, value) = scan!(s, "mem[{}] = {}", usize, u64)?;
         Ok(Command::Write((address, value)))
     }
 }
 
-fn parse_input() -> Result<Vec<Command>, ParseError> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| parse_command(v))
-        .collect::<Result<Vec<_>, ParseError>>()
-}
-
-pub fn problem1() -> Result<(), ParseError> {
-    let commands = parse_input()?;
-
-    let buffer_max = commands.iter()
-        .map(|c| match c {
-            Command::Write((a, _)) => *a,
-            Command::Mask(_) => 0,
-        })
-        .max()
-        .ok_or(ParseError::new("Could not determine memory size."))?;
-
-    let mut memory = vec![0; buffer_max + 1];
-    let mut mask = (0, 0);
-
-    for c in &commands {
-        match c {
-            Command::Mask(m) => {
-                mask = *m;
-            },
-            Command::Write((a, v)) => {
-                memory[*a] = (v & mask.0) | mask.1;
-            }
-        }
-    }
-
-    let result: u64 = memory.iter()
-        .filter(|&v| *v != 0)
-        .sum();
+pub const INPUT: &str = include_str!("./data/input.txt");
 
-    println!("14/1: memory init result is: {}", result);
-
-    Ok(())
+pub fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_lines(input, parse_command)
 }
 
-fn variants(mask: usize) -> Vec<usize> {
+pub fn variants(mask: usize) -> Vec<usize> {
     let mut variants = HashSet::new();
     variants.insert(0);
 
     for i in 0..36 {
-        let bit = (1_usize << i) as usize;
+        let bit = 1_usize << i;
 
         let candidate = mask & bit;
         if candidate != 0 {
@@ -114,7 +72,7 @@ fn variants(mask: usize) -> Vec<usize> {
     variants.into_iter().collect()
 }
 
-fn write(memory: &mut HashMap<usize, u64>, mask: u64, overwrite: u64, address: usize, value: u64) {
+pub fn write(memory: &mut HashMap<usize, u64>, mask: u64, overwrite: u64, address: usize, value: u64) {
     let overwrite = overwrite as usize;
     let mask = mask as usize;
 
@@ -127,28 +85,62 @@ fn write(memory: &mut HashMap<usize, u64>, mask: u64, overwrite: u64, address: u
     }
 }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let commands = parse_input()?;
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Command>;
 
-    let mut memory = HashMap::new();
-    let mut mask = (0, 0);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    for c in &commands {
-        match c {
-            Command::Mask(m) => {
-                mask = *m;
-            },
-            Command::Write((a, v)) => {
-                write(&mut memory, mask.0, mask.1, *a, *v);
+    fn part1(commands: &Self::Input) -> Result<Answer, Error> {
+        let buffer_max = commands.iter()
+            .map(|c| match c {
+                Command::Write((a, _)) => *a,
+                Command::Mask(_) => 0,
+            })
+            .max()
+            .ok_or(Error::solve("Could not determine memory size."))?;
+
+        let mut memory = vec![0; buffer_max + 1];
+        let mut mask = (0, 0);
+
+        for c in commands {
+            match c {
+                Command::Mask(m) => {
+                    mask = *m;
+                },
+                Command::Write((a, v)) => {
+                    memory[*a] = (v & mask.0) | mask.1;
+                }
             }
         }
+
+        let result: u64 = memory.iter()
+            .filter(|&v| *v != 0)
+            .sum();
+
+        Ok(Answer::from(result))
     }
 
-    let result: u64 = memory.iter()
-        .map(|(_, v)| *v)
-        .sum();
+    fn part2(commands: &Self::Input) -> Result<Answer, Error> {
+        let mut memory = HashMap::new();
+        let mut mask = (0, 0);
+
+        for c in commands {
+            match c {
+                Command::Mask(m) => {
+                    mask = *m;
+                },
+                Command::Write((a, v)) => {
+                    write(&mut memory, mask.0, mask.1, *a, *v);
+                }
+            }
+        }
 
-    println!("14/2: memory init result is: {}", result);
+        let result: u64 = memory.values().sum();
 
-    Ok(())
+        Ok(Answer::from(result))
+    }
 }
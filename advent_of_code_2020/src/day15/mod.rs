@@ -1,8 +1,26 @@
 use std::collections::HashMap;
-use super::utils::ParseError;
+use super::cancel;
+use super::progress;
+use super::utils::{parse, Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
+
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    Input::new(input)
+        .line()?
+        .split(',')
+        .map(|v| parse::<i32>(v).map_err(|e| e.locate(input, v)))
+        .collect::<Result<Vec<_>, ParseError>>()
+}
 
-fn run(start: Vec<i32>, max: usize) -> Result<i32, ParseError> {
+pub fn run(start: Vec<i32>, max: usize) -> Result<i32, Error> {
     let s = start.len();
+    if s == 0 || s > max {
+        return Err(Error::solve(&format!("Expected 1 to {} starting numbers, found {}", max, s)));
+    }
+
     let mut m = HashMap::new();
 
     // don't store the last known index of the last number, this is done in the
@@ -16,8 +34,11 @@ fn run(start: Vec<i32>, max: usize) -> Result<i32, ParseError> {
     let mut start = start;
     record.append(&mut start);
 
+    let mut task = progress::task("Speaking numbers", (max - s) as u64);
     for i in s..max {
-        let last = *record.get(record.len() - 1).ok_or(ParseError::new("Empty record found."))?;
+        task.inc(1);
+        cancel::checkpoint()?;
+        let last = *record.last().ok_or(Error::solve("Empty record found."))?;
         let last_index = m.get(&last);
 
         if let Some(n) = last_index {
@@ -33,22 +54,24 @@ fn run(start: Vec<i32>, max: usize) -> Result<i32, ParseError> {
     Ok(*record.last().unwrap())
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = vec![1,0,15,2,10,13];
+pub struct Day15;
 
-    let result = run(input, 2020)?;
-    println!("15/1: last number is {}", result);
+impl Solution for Day15 {
+    type Input = Vec<i32>;
 
-    Ok(())
-}
-
-pub fn problem2() -> Result<(), ParseError> {
-    let input = vec![1,0,15,2,10,13];
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    let result = run(input, 30_000_000)?;
-    println!("15/2: last number is {}", result);
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let result = run(input.clone(), 2020)?;
+        Ok(Answer::from(result))
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let result = run(input.clone(), 30_000_000)?;
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
@@ -58,18 +81,24 @@ mod test {
     #[test]
     pub fn example_1_1() {
         let input = vec![0, 3, 6];
-        assert_eq!(436, run(input, 2020).unwrap());
+        assert_eq!(436, run(input.clone(), 2020).unwrap());
     }
 
     #[test]
     pub fn example_1_2() {
         let input = vec![2, 3, 1];
-        assert_eq!(78, run(input, 2020).unwrap());
+        assert_eq!(78, run(input.clone(), 2020).unwrap());
     }
 
     #[test]
     pub fn example_1_3() {
         let input = vec![3,1,2];
-        assert_eq!(1836, run(input, 2020).unwrap());
+        assert_eq!(1836, run(input.clone(), 2020).unwrap());
+    }
+
+    #[test]
+    pub fn more_starting_numbers_than_turns_are_rejected() {
+        let input = (0..2021).collect::<Vec<_>>();
+        assert!(run(input, 2020).is_err());
     }
 }
\ No newline at end of file
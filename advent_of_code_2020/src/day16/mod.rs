@@ -1,25 +1,26 @@
 use std::hash::Hasher;
 use std::hash::Hash;
 use std::collections::HashSet;
-use regex::Regex;
-use super::utils::ParseError;
+use super::utils::{parse, Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(Debug)]
-struct Range {
-    min: u32,
-    max: u32,
+pub struct Range {
+    pub min: u32,
+    pub max: u32,
 }
 
 impl Range {
-    fn is_valid(&self, i: u32) -> bool {
+    pub fn is_valid(&self, i: u32) -> bool {
         self.min <= i && i <= self.max
     }
 }
 
 #[derive(Debug)]
-struct Rule {
-    description: String,
-    ranges: Vec<Range>,
+pub struct Rule {
+    pub description: String,
+    pub ranges: Vec<Range>,
 }
 
 impl PartialEq for Rule {
@@ -37,113 +38,85 @@ impl Hash for Rule {
 }
 
 impl Rule {
-    fn is_valid(&self, i: u32) -> bool {
+    pub fn is_valid(&self, i: u32) -> bool {
         self.ranges.iter().any(|r| r.is_valid(i))
     }
 }
 
-type Ticket = Vec<u32>;
+pub type Ticket = Vec<u32>;
 
 #[derive(Debug)]
-struct Puzzle {
-    rules: Vec<Rule>,
-    my_ticket: Ticket,
-    nearby_tickets: Vec<Ticket>,
+pub struct Puzzle {
+    pub rules: Vec<Rule>,
+    pub my_ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
 }
 
-fn read_rule(s: &str) -> Rule {
-    lazy_static!{
-        static ref RE: Regex = Regex::new(r"^(.+?): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
-    }
-
-    let cap = RE.captures(s).unwrap();
-    let description = cap[1].to_string();
-
-    let mut ranges = vec![];
-
-    let min = cap[2].parse::<u32>().unwrap();
-    let max = cap[3].parse::<u32>().unwrap();
-    ranges.push(Range { min, max });
+pub fn read_rule(s: &str) -> Result<Rule, ParseError> {
+    let (description, min1, max1, min2, max2) = scan!(s, "{}: {}-{} or {}-{}", String, u32, u32, u32, u32)?;
+    let ranges = vec![Range { min: min1, max: max1 }, Range { min: min2, max: max2 }];
 
-    let min = cap[4].parse::<u32>().unwrap();
-    let max = cap[5].parse::<u32>().unwrap();
-    ranges.push(Range { min, max });
-
-    Rule { description, ranges }
+    Ok(Rule { description, ranges })
 }
 
-fn read_ticket(s: &str) -> Ticket {
+pub fn read_ticket(s: &str) -> Result<Ticket, ParseError> {
     s.split(',')
         .filter(|v| !v.is_empty())
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
+        .map(parse::<u32>)
+        .collect::<Result<Vec<_>, ParseError>>()
 }
 
-fn parse_input() -> Puzzle {
-    let input = include_str!("./data/input.txt");
-    let puzzle = input
-        .split("\n\n")
-        .filter(|v| *v != "")
-        .collect::<Vec<_>>();
+pub const INPUT: &str = include_str!("./data/input.txt");
 
+pub fn parse_input(input: &str) -> Result<Puzzle, ParseError> {
+    let puzzle = Input::new(input).blocks();
+
+    if puzzle.len() != 3 {
+        return Err(ParseError::new(&format!("Expected rules, your ticket and nearby tickets, found {} sections", puzzle.len())));
+    }
+
+    // lines are sliced from the input, so errors can be located in it
     let rules = puzzle[0].lines()
-        .filter(|v| !v.is_empty())
-        .map(|l| read_rule(l))
-        .collect::<Vec<_>>();
+        .map(|l| read_rule(l).map_err(|e| e.locate(input, l)))
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
     let my_ticket = puzzle[1].lines()
-        .skip(1)
-        .map(|t| read_ticket(t))
-        .next().unwrap();
+        .nth(1)
+        .ok_or(ParseError::new("Missing your ticket").locate(input, puzzle[1].text()))
+        .and_then(|t| read_ticket(t).map_err(|e| e.locate(input, t)))?;
 
     let nearby_tickets = puzzle[2].lines()
         .skip(1)
-        .filter(|v| !v.is_empty())
-        .map(|l| read_ticket(l))
-        .collect::<Vec<_>>();
+        .map(|l| read_ticket(l).map_err(|e| e.locate(input, l)))
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
-    Puzzle { rules, my_ticket, nearby_tickets }
+    Ok(Puzzle { rules, my_ticket, nearby_tickets })
 }
 
-fn is_valid_for_some_field(rules: &Vec<Rule>, n: u32) -> bool {
+pub fn is_valid_for_some_field(rules: &[Rule], n: u32) -> bool {
     rules.iter()
         .any(|r| r.is_valid(n))
 }
 
-fn find_invalid_fields(rules: &Vec<Rule>, ticket: &Ticket) -> Vec<u32> {
+pub fn find_invalid_fields(rules: &[Rule], ticket: &Ticket) -> Vec<u32> {
     ticket.iter()
         .filter(|n| !is_valid_for_some_field(rules, **n))
         .cloned()
         .collect::<Vec<_>>()
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = parse_input();
-
-    let mut invalid_fields = vec![];
-    for t in &input.nearby_tickets {
-        let mut invalid_fields_for_ticket = find_invalid_fields(&input.rules, t);
-        invalid_fields.append(&mut invalid_fields_for_ticket);
-    }
-
-    let result: u32 = invalid_fields.iter().sum();
-    println!("16/1: sum of invalid fields is {}", result);
-
-    Ok(())
-}
-
-fn is_valid(rules: &Vec<Rule>, ticket: &Ticket) -> bool {
+pub fn is_valid(rules: &[Rule], ticket: &Ticket) -> bool {
     ticket.iter()
         .all(|n| is_valid_for_some_field(rules, *n))
 }
 
-fn determine_valid_rules(rules: &Vec<Rule>, n: u32) -> HashSet<&Rule> {
+pub fn determine_valid_rules(rules: &[Rule], n: u32) -> HashSet<&Rule> {
     rules.iter()
         .filter(|r| r.is_valid(n))
         .collect::<HashSet<_>>()
 }
 
-fn determine_valid_rules_2(rules: &Vec<Rule>, n: u32) -> HashSet<usize> {
+pub fn determine_valid_rules_2(rules: &[Rule], n: u32) -> HashSet<usize> {
     rules.iter()
         .enumerate()
         .filter(|(_, r)| r.is_valid(n))
@@ -151,57 +124,75 @@ fn determine_valid_rules_2(rules: &Vec<Rule>, n: u32) -> HashSet<usize> {
         .collect::<HashSet<_>>()
 }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let input = parse_input();
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Puzzle;
 
-    let valid_nearby_tickets = input.nearby_tickets.iter()
-        .filter(|t| is_valid(&input.rules, t))
-        .collect::<Vec<_>>();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    let len = input.rules.len();
-    let mut candidates = vec![];
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let mut invalid_fields = vec![];
+        for t in &input.nearby_tickets {
+            let mut invalid_fields_for_ticket = find_invalid_fields(&input.rules, t);
+            invalid_fields.append(&mut invalid_fields_for_ticket);
+        }
 
-    for i in 0..len {
+        let result: u32 = invalid_fields.iter().sum();
+        Ok(Answer::from(result))
+    }
 
-        let valid_rules = valid_nearby_tickets.iter()
-            .map(|t| determine_valid_rules(&input.rules, t[i]))
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let valid_nearby_tickets = input.nearby_tickets.iter()
+            .filter(|t| is_valid(&input.rules, t))
             .collect::<Vec<_>>();
 
-        let mut rules_iter = valid_rules.iter();
-        let mut one_rule = rules_iter.next().cloned().unwrap();
+        let len = input.rules.len();
+        let mut candidates = vec![];
 
-        for r in rules_iter {
-            one_rule = one_rule.intersection(&r).cloned().collect();
-        }
+        for i in 0..len {
 
-        candidates.push((i, one_rule));
-    }
+            let valid_rules = valid_nearby_tickets.iter()
+                .map(|t| determine_valid_rules(&input.rules, t[i]))
+                .collect::<Vec<_>>();
 
-    candidates.sort_by(|a, b| a.1.len().cmp(&b.1.len()));
-    let mut already_assigned = HashSet::new();
+            let mut rules_iter = valid_rules.iter();
+            let mut one_rule = rules_iter.next().cloned()
+                .ok_or(Error::solve("There are no valid nearby tickets"))?;
 
-    let mut columns = vec![];
-    for c in &mut candidates {
-        for a in &already_assigned {
-            c.1.remove(a);
-        }
+            for r in rules_iter {
+                one_rule = one_rule.intersection(r).cloned().collect();
+            }
 
-        if c.1.len() != 1 {
-            panic!("could not uniquely determine which column corresponds to which field");
+            candidates.push((i, one_rule));
         }
 
-        let next_column = c.1.iter().next().unwrap();
-        columns.push((c.0, next_column));
-        already_assigned.insert(*next_column);
-    }
+        candidates.sort_by_key(|a| a.1.len());
+        let mut already_assigned = HashSet::new();
 
-    let readable_candidates = columns.iter().map(|(i, c)| (i, c.description.clone())).collect::<Vec<_>>();
+        let mut columns = vec![];
+        for c in &mut candidates {
+            for a in &already_assigned {
+                c.1.remove(a);
+            }
 
-    let result: u64 = readable_candidates.iter()
-        .filter(|(_, d)| d.contains("departure"))
-        .map(|(i, _)| input.my_ticket[**i] as u64)
-        .product();
-    println!("16/2: sum of invalid fields is {}", result);
+            if c.1.len() != 1 {
+                return Err(Error::solve("Could not uniquely determine which column corresponds to which field"));
+            }
 
-    Ok(())
+            let next_column = c.1.iter().next().unwrap();
+            columns.push((c.0, next_column));
+            already_assigned.insert(*next_column);
+        }
+
+        let readable_candidates = columns.iter().map(|(i, c)| (i, c.description.clone())).collect::<Vec<_>>();
+
+        let result: u64 = readable_candidates.iter()
+            .filter(|(_, d)| d.contains("departure"))
+            .map(|(i, _)| input.my_ticket[**i] as u64)
+            .product();
+        Ok(Answer::from(result))
+    }
 }
@@ -1,15 +1,50 @@
 use std::collections::VecDeque;
-use super::utils::ParseError;
+use super::utils::{Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
-fn load_input() -> Vec<&'static str> {
-    let input = include_str!("./data/input.txt");
-    input
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+/// Makes sure `line` is an expression the evaluators can handle: single
+/// digits and parentheses joined by `+` and `*`.
+fn check_expression(input: &str, line: &str) -> Result<(), ParseError> {
+    let error = |what: &str, span: &str| ParseError::new(what).locate(input, span);
+
+    let mut expect_operand = true;
+    let mut depth = 0;
+    for (p, c) in line.char_indices() {
+        let span = &line[p..p + c.len_utf8()];
+        match c {
+            ' ' => {},
+            '0'..='9' | '(' if !expect_operand => return Err(error("Expected an operator", span)),
+            '+' | '*' | ')' if expect_operand => return Err(error("Expected a number", span)),
+            '0'..='9' => expect_operand = false,
+            '(' => depth += 1,
+            '+' | '*' => expect_operand = true,
+            ')' if depth == 0 => return Err(error("Unmatched ')'", span)),
+            ')' => depth -= 1,
+            _ => return Err(error(&format!("Unexpected char: {}", c), span)),
+        }
+    }
+
+    if expect_operand {
+        return Err(error("Expected a number at the end of the expression", line));
+    }
+    if depth > 0 {
+        return Err(error("Unclosed '('", line));
+    }
+
+    Ok(())
+}
+
+pub fn load_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Input::new(input)
         .lines()
-        .filter(|v| *v != "")
-        .collect::<Vec<_>>()
+        .map(|v| check_expression(input, v).map(|_| v.to_string()))
+        .collect::<Result<Vec<_>, _>>()
 }
 
-fn evaluate(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>) -> u64 {
+pub fn evaluate(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>) -> u64 {
     while !ops.is_empty() {
         let op = ops.pop_front();
         let v1 = values.pop_front().unwrap();
@@ -25,7 +60,7 @@ fn evaluate(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>) -> u64 {
     values.pop_front().unwrap()
 }
 
-fn evaluate_with_precedence(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>) -> u64 {
+pub fn evaluate_with_precedence(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>) -> u64 {
     let mut mult_ops = VecDeque::new();
     let mut mult_vals = VecDeque::new();
 
@@ -68,7 +103,7 @@ fn evaluate_with_precedence(values: &mut VecDeque<u64>, ops: &mut VecDeque<char>
     evaluate(&mut values, &mut ops)
 }
 
-fn parse<F>(s: &str, pos: usize, evaluator: &F) -> (usize, u64)
+pub fn parse<F>(s: &str, pos: usize, evaluator: &F) -> (usize, u64)
     where F: Fn(&mut VecDeque<u64>, &mut VecDeque<char>) -> u64 {
 
         let mut stack = VecDeque::new();
@@ -110,26 +145,28 @@ fn parse<F>(s: &str, pos: usize, evaluator: &F) -> (usize, u64)
     (0, evaluator(&mut stack, &mut ops))
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = load_input();
-
-    let result: u64 = input.iter()
-        .map(|s| parse(s, 0, &evaluate).1)
-        .sum();
-    println!("18/1: sum over all expressions is {}", result);
+pub struct Day18;
 
-    Ok(())
-}
+impl Solution for Day18 {
+    type Input = Vec<String>;
 
-pub fn problem2() -> Result<(), ParseError> {
-    let input = load_input();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(load_input(input)?)
+    }
 
-    let result: u64 = input.iter()
-        .map(|s| parse(s, 0, &evaluate_with_precedence).1)
-        .sum();
-    println!("18/2: sum over all expressions is {}", result);
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let result: u64 = input.iter()
+            .map(|s| parse(s, 0, &evaluate).1)
+            .sum();
+        Ok(Answer::from(result))
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let result: u64 = input.iter()
+            .map(|s| parse(s, 0, &evaluate_with_precedence).1)
+            .sum();
+        Ok(Answer::from(result))
+    }
 }
 
 #[cfg(test)]
@@ -171,4 +208,14 @@ mod test {
         let input = "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
         assert_eq!(669060, parse(input, 0, &evaluate_with_precedence).1);
     }
+
+    #[test]
+    pub fn malformed_expressions_are_rejected() {
+        assert!(load_input("1 + (2 * 3)\n").is_ok());
+        assert!(load_input("1 + 2 *\n").is_err());
+        assert!(load_input("1 + (2 * 3\n").is_err());
+        assert!(load_input("1 + 2) * 3\n").is_err());
+        assert!(load_input("1 + 2 - 3\n").is_err());
+        assert!(load_input("1 2 + 3\n").is_err());
+    }
 }
\ No newline at end of file
@@ -1,31 +1,36 @@
 use std::collections::HashMap;
-use super::utils::ParseError;
+use super::utils::{self, Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 use dynparser::{parse, rules_from_peg};
 
-type Rules<'a> = Vec<&'a str>;
-type Input<'a> = Vec<&'a str>;
+pub type Rules = Vec<String>;
+pub type Messages = Vec<String>;
 
-fn parse_input() -> (Rules<'static>, Input<'static>) {
-    let raw_input = include_str!("./data/input.txt");
-    let rules_and_input = raw_input
-        .split("\n\n")
-        .collect::<Vec<_>>();
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<(Rules, Messages), ParseError> {
+    let rules_and_input = Input::new(input).blocks();
+
+    if rules_and_input.len() < 2 {
+        return Err(ParseError::new("Expected rules and messages separated by an empty line"));
+    }
 
     let rules = rules_and_input[0]
         .lines()
-        .filter(|v| *v != "")
-        .collect::<Vec<_>>();
+        .map(|v| parse_tree(vec![v]).map(|_| v.to_string()).map_err(|e| e.locate(input, v)))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let input = rules_and_input[1]
+    let messages = rules_and_input[1]
         .lines()
-        .filter(|v| *v != "")
+        .map(|v| v.to_string())
         .collect::<Vec<_>>();
 
-    (rules, input)
+    Ok((rules, messages))
 }
 
-fn convert_rules_to_peg(rules: Rules) -> String {
-    let mut rules = rules;
+pub fn convert_rules_to_peg(rules: &Rules) -> String {
+    let mut rules = rules.iter().map(|r| r.as_str()).collect::<Vec<_>>();
 
     // dynparser expects one rule called 'main' as an entry point
     // Since rule '0' corresponds to this in our grammar, we just define one
@@ -41,85 +46,74 @@ fn convert_rules_to_peg(rules: Rules) -> String {
     let ortrta = ortrta.replace("|", "\n  /");
     let ortrta = ortrta.replace(":", " =");
     let ortrta = ortrta.replace("\"a\"", r#"'a'"#);
-    let ortrta = ortrta.replace("\"b\"", r#"'b'"#);
 
-    ortrta
-}
-
-pub fn problem1() -> Result<(), ParseError> {
-    let (rules, input) = parse_input();
-
-    let prepared_rules = convert_rules_to_peg(rules);
-
-    let peg_rules = rules_from_peg(&prepared_rules).unwrap();
-
-    let result = input.iter()
-        .map(|v| parse(v, &peg_rules).is_ok())
-        .filter(|m| *m)
-        .count();
-
-    println!("19/1: # of successfully parsed input lines: {}", result);
-
-    Ok(())
+    ortrta.replace("\"b\"", r#"'b'"#)
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum Element {
+pub enum Element {
     Expression(usize),
     Leaf(String),
 }
 
-fn parse_rhs(rhs: &str) -> Vec<Element> {
+pub fn parse_rhs(rhs: &str) -> Result<Vec<Element>, ParseError> {
     rhs.split(" ")
         .map(|e| {
             if e.contains("a") {
-                return Element::Leaf("a".to_owned());
+                return Ok(Element::Leaf("a".to_owned()));
             }
 
             if e.contains("b") {
-                return Element::Leaf("b".to_owned());
+                return Ok(Element::Leaf("b".to_owned()));
             }
 
-            Element::Expression(e.parse::<usize>().unwrap())
+            Ok(Element::Expression(utils::parse::<usize>(e)?))
         })
-        .collect::<Vec<_>>()
+        .collect::<Result<Vec<_>, _>>()
 }
 
-fn parse_tree(rules: Vec<&str>) -> HashMap<usize, Vec<Vec<Element>>> {
+pub fn parse_tree(rules: Vec<&str>) -> Result<HashMap<usize, Vec<Vec<Element>>>, ParseError> {
     let mut map = HashMap::new();
 
     for rule in &rules {
         let index_rule = rule.split(": ").collect::<Vec<_>>();
-        let index = index_rule[0].parse::<usize>().unwrap();
+        if index_rule.len() != 2 {
+            return Err(ParseError::new("Expected a rule like '<index>: <rule>'"));
+        }
+        let index = utils::parse::<usize>(index_rule[0])?;
 
         let rule = index_rule[1].split(" | ")
-            .map(|v| parse_rhs(v))
-            .collect::<Vec<_>>();
+            .map(parse_rhs)
+            .collect::<Result<Vec<_>, _>>()?;
 
         map.entry(index).or_insert(rule);
     }
 
-    map
+    Ok(map)
 }
 
-fn test(s: &str, rules: &HashMap<usize, Vec<Vec<Element>>>, todo: Vec<usize>) -> bool {
-    if todo.len() == 0 {
-        return s.len() == 0;
+pub fn test(s: &str, rules: &HashMap<usize, Vec<Vec<Element>>>, todo: Vec<usize>) -> bool {
+    if todo.is_empty() {
+        return s.is_empty();
     }
 
     let next = todo[0];
-    let others: Vec<usize> = todo[1..].iter().cloned().collect();
-    let descend = rules.get(&next).unwrap().clone();
+    let others: Vec<usize> = todo[1..].to_vec();
+    // a message can't match a rule that doesn't exist
+    let descend = match rules.get(&next) {
+        Some(descend) => descend.clone(),
+        None => return false,
+    };
 
     // we found a leaf ('a' or 'b')
     if descend.len() == 1 && descend[0].len() == 1 {
-        if s.len() == 0 {
+        if s.is_empty() {
             return false;
         }
         let f = &descend[0][0];
 
         if &Element::Leaf(s[0..1].to_string()) == f {
-            return test(&s[1..].to_string(), rules, others.clone());
+            return test(&s[1..], rules, others.clone());
         } else if let &Element::Leaf(_) = f {
             return false;
         }
@@ -146,32 +140,53 @@ fn test(s: &str, rules: &HashMap<usize, Vec<Vec<Element>>>, todo: Vec<usize>) ->
         })
 }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let (rules, input) = parse_input();
+pub struct Day19;
 
-    let mut patched_rules = rules.into_iter()
-        .filter(|r| !r.starts_with("8:") && !r.starts_with("11:"))
-        .collect::<Vec<_>>();
+impl Solution for Day19 {
+    type Input = (Rules, Messages);
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    patched_rules.push("8: 42 | 42 8");
-    patched_rules.push("11: 42 31 | 42 11 31");
+    fn part1((rules, messages): &Self::Input) -> Result<Answer, Error> {
+        let prepared_rules = convert_rules_to_peg(rules);
 
-    let tree = parse_tree(patched_rules);
+        let peg_rules = rules_from_peg(&prepared_rules)
+            .map_err(|e| Error::solve(&format!("Unable to build the grammar: {:?}", e)))?;
 
-    let main_rule: Vec<usize> = tree.get(&0).unwrap()[0].iter()
-        .filter_map(|v| if let Element::Expression(e) = v {
-            Some(e)
-        } else {
-            None
-        })
-        .cloned()
-        .collect();
+        let result = messages.iter()
+            .map(|v| parse(v, &peg_rules).is_ok())
+            .filter(|m| *m)
+            .count();
+
+        Ok(Answer::from(result))
+    }
+
+    fn part2((rules, messages): &Self::Input) -> Result<Answer, Error> {
+        let mut patched_rules = rules.iter()
+            .map(|r| r.as_str())
+            .filter(|r| !r.starts_with("8:") && !r.starts_with("11:"))
+            .collect::<Vec<_>>();
 
-    let result = input.iter()
-        .filter(|i| test(i, &tree, main_rule.clone()))
-        .count();
+        patched_rules.push("8: 42 | 42 8");
+        patched_rules.push("11: 42 31 | 42 11 31");
 
-    println!("19/2: # of successfully parsed input lines: {}", result);
+        let tree = parse_tree(patched_rules)?;
 
-    Ok(())
+        let main_rule: Vec<usize> = tree.get(&0).ok_or(Error::solve("There is no rule 0"))?[0].iter()
+            .filter_map(|v| if let Element::Expression(e) = v {
+                Some(e)
+            } else {
+                None
+            })
+            .cloned()
+            .collect();
+
+        let result = messages.iter()
+            .filter(|i| test(i, &tree, main_rule.clone()))
+            .count();
+
+        Ok(Answer::from(result))
+    }
 }
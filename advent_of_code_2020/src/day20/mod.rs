@@ -1,101 +1,105 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-mod tile;
-mod tilehash;
-mod tileconnection;
-mod patterns;
-mod reconstruct;
-
-use super::utils::ParseError;
+pub mod tile;
+pub mod tilehash;
+pub mod tileconnection;
+pub mod patterns;
+pub mod reconstruct;
+
+use super::utils::{Error, Input, ParseError};
+use super::utils::grid::Grid;
+use super::answer::Answer;
+use super::solution::Solution;
 use patterns::{transform_and_find_pattern, remove_pattern};
 use reconstruct::reconstruct_image;
 use tile::Tile;
 
-fn parse_input() -> Result<Vec<Tile>, ParseError> {
-    let input = include_str!("./data/input.txt");
-    input
-        .split("\n\n")
-        .filter(|v| *v != "")
-        .map(|v| Tile::from_str(v))
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<Tile>, ParseError> {
+    Input::new(input)
+        .blocks()
+        .iter()
+        .map(|v| Tile::from_str(v.text()).map_err(|e| e.locate(input, v.text())))
         .collect::<Result<Vec<_>, ParseError>>()
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = parse_input()?;
+pub fn get_monster() -> Grid<char> {
+    Input::new(include_str!("./data/monster.txt"))
+        .grid()
+        .expect("The monster is a grid")
+}
 
-    let hashes = input.iter()
-        .map(|t| t.hashes())
-        .collect::<Vec<_>>();
+pub struct Day20;
 
-    let result: u64 = hashes.iter()
-        .map(|h| (h.id(), h.number_of_neighbors(&hashes)))
-        .filter(|n| n.1 == 2)
-        .map(|h| h.0)
-        .product();
+impl Solution for Day20 {
+    type Input = Vec<Tile>;
 
-    println!("20/1: Product of the ids of all four corners of the map: {}", result);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    Ok(())
-}
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let hashes = input.iter()
+            .map(|t| t.hashes())
+            .collect::<Vec<_>>();
 
-fn get_monster() -> Vec<Vec<char>> {
-    include_str!("./data/monster.txt")
-        .lines()
-        .map(|v| v.chars().collect())
-        .collect()
-}
+        let result: u64 = hashes.iter()
+            .map(|h| (h.id(), h.number_of_neighbors(&hashes)))
+            .filter(|n| n.1 == 2)
+            .map(|h| h.0)
+            .product();
 
-pub fn problem2() -> Result<(), ParseError> {
-    let tiles = parse_input()?;
-
-    let hashes = tiles.iter()
-        .map(|t| t.hashes())
-        .collect::<Vec<_>>();
-
-    let relations = hashes.iter()
-        .map(|h| (h.id(), h.find_neighbors(&hashes)))
-        .collect::<Vec<_>>();
-
-    // Both the example and my input have a corner that can be considered "top
-    // left" without rotation or flipping the image.
-    // "Top left" is defined as the tile that has two neighbors and the
-    // neighbors are to the right and below the top left tile, i.e.
-    //   my_border = [1, 2]
-    let top_left = &relations.iter()
-        // find corners
-        .filter(|r| r.1.len() == 2)
-        // find corner with neighbors to the right (my_border == 1) and bottom
-        // (my_border == 2) of the corner
-        .filter(|r| {
-            let my_borders = r.1.iter().map(|v| v.my_border).collect::<Vec<_>>();
-            my_borders.contains(&1) && my_borders.contains(&2)
-        })
-        .next().unwrap();
-
-    let connections = relations.iter()
-        .map(|v| &v.1)
-        .cloned()
-        .flatten()
-        .collect::<Vec<_>>();
-
-    let mut connections_map = HashMap::new();
-    for c in &connections {
-        connections_map.entry((c.id, c.my_border)).or_insert(c);
+        Ok(Answer::from(result))
     }
 
-    // construct the image based on the tile connections
-    let image = reconstruct_image(&tiles, &connections, top_left.0);
+    fn part2(tiles: &Self::Input) -> Result<Answer, Error> {
+        let hashes = tiles.iter()
+            .map(|t| t.hashes())
+            .collect::<Vec<_>>();
+
+        let relations = hashes.iter()
+            .map(|h| (h.id(), h.find_neighbors(&hashes)))
+            .collect::<Vec<_>>();
+
+        // Both the example and my input have a corner that can be considered "top
+        // left" without rotation or flipping the image.
+        // "Top left" is defined as the tile that has two neighbors and the
+        // neighbors are to the right and below the top left tile, i.e.
+        //   my_border = [1, 2]
+        let top_left = &relations.iter()
+            // find corners
+            .filter(|r| r.1.len() == 2)
+            // find corner with neighbors to the right (my_border == 1) and bottom
+            // (my_border == 2) of the corner
+            .find(|r| {
+                let my_borders = r.1.iter().map(|v| v.my_border).collect::<Vec<_>>();
+                my_borders.contains(&1) && my_borders.contains(&2)
+            })
+            .ok_or(Error::solve("There is no top left corner"))?;
+
+        let connections = relations.iter()
+            .flat_map(|v| &v.1)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut connections_map = HashMap::new();
+        for c in &connections {
+            connections_map.entry((c.id, c.my_border)).or_insert(c);
+        }
+
+        // construct the image based on the tile connections
+        let image = reconstruct_image(tiles, &connections, top_left.0);
+
+        // rotate and flip image and look for monsters
+        let monster = get_monster();
+        let (transformed, monsters) = transform_and_find_pattern(&image.data, &monster)
+            .ok_or(Error::solve("No monsters found!"))?;
 
-    // rotate and flip image and look for monsters
-    let monster = get_monster();
-    if let Some((transformed, monsters)) = transform_and_find_pattern(&image, &monster) {
         let image_without_monsters = remove_pattern(transformed, &monster, &monsters);
-        let result = image_without_monsters.count('#');
-        println!("20/2: water roughness: {}", result);
-    } else {
-        println!("No monsters found!");
-    }
+        let result = image_without_monsters.count(|c| *c == '#');
 
-    Ok(())
+        Ok(Answer::from(result))
+    }
 }
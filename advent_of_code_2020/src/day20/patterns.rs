@@ -1,29 +1,15 @@
-use super::tile::Tile;
+use crate::utils::grid::{Grid, Pos};
 
-pub type Coords = (usize, usize);
-
-fn find_pattern(image: &Tile, pattern: &Vec<Vec<char>>) -> Vec<Coords> {
-    let size_image = image.data.len();
-    let height_pattern = pattern.len();
-    let width_pattern = pattern[0].len();
+pub fn find_pattern(image: &Grid<char>, pattern: &Grid<char>) -> Vec<Pos> {
+    let marks = pattern.iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
 
     let mut positions = vec![];
-    for y in 0..(size_image-height_pattern) {
-        for x in 0..(size_image-width_pattern) {
-            let mut found = true;
-            for my in 0..height_pattern {
-                for mx in 0..width_pattern {
-                    if pattern[my][mx] == '#' && image.data[y + my][x + mx] != '#' {
-                        found = false;
-                        break;
-                    }
-                }
-                if !found {
-                    break;
-                }
-            }
-
-            if found {
+    for y in 0..(image.height() - pattern.height()) as i64 {
+        for x in 0..(image.width() - pattern.width()) as i64 {
+            if marks.iter().all(|(mx, my)| image[(x + mx, y + my)] == '#') {
                 positions.push((x, y));
             }
         }
@@ -32,33 +18,25 @@ fn find_pattern(image: &Tile, pattern: &Vec<Vec<char>>) -> Vec<Coords> {
     positions
 }
 
-pub fn transform_and_find_pattern(image: &Tile, pattern: &Vec<Vec<char>>) -> Option<(Tile, Vec<Coords>)> {
-    for r in 0..4 {
-        for f in vec![true, false] {
-            let transformed = image.transform(r, f, false);
+pub fn transform_and_find_pattern(image: &Grid<char>, pattern: &Grid<char>) -> Option<(Grid<char>, Vec<Pos>)> {
+    image.orientations()
+        .into_iter()
+        .map(|transformed| {
             let positions = find_pattern(&transformed, pattern);
-            if !positions.is_empty() {
-                return Some((transformed, positions));
-            }
-        }
-    }
-
-    None
+            (transformed, positions)
+        })
+        .find(|(_, positions)| !positions.is_empty())
 }
 
-pub fn remove_pattern(image: Tile, pattern: &Vec<Vec<char>>, positions: &Vec<Coords>) -> Tile {
-    let height_pattern = pattern.len();
-    let width_pattern = pattern[0].len();
+pub fn remove_pattern(image: Grid<char>, pattern: &Grid<char>, positions: &Vec<Pos>) -> Grid<char> {
     let mut image = image;
 
     for p in positions {
-        for y in 0..height_pattern {
-            for x in 0..width_pattern {
-                if pattern[y][x] == '#' {
-                    image.data[p.1 + y][p.0 + x] = 'O';
-                }
+        for ((x, y), c) in pattern.iter() {
+            if c == &'#' {
+                image[(p.0 + x, p.1 + y)] = 'O';
             }
         }
     }
     image
-}
\ No newline at end of file
+}
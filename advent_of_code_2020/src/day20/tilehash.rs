@@ -2,8 +2,8 @@ use super::tileconnection::TileConnection;
 
 #[derive(Debug)]
 pub struct TileHash {
-    id: u64,
-    data: Vec<u64>,
+    pub id: u64,
+    pub data: Vec<u64>,
 }
 
 impl TileHash {
@@ -24,11 +24,11 @@ impl TileHash {
         flip
     }
 
-    pub fn number_of_neighbors(&self, all_hashes: &Vec<TileHash>) -> u64 {
+    pub fn number_of_neighbors(&self, all_hashes: &[TileHash]) -> u64 {
         self.find_neighbors(all_hashes).len() as u64
     }
 
-    pub fn find_neighbors(&self, all_hashes: &Vec<TileHash>) -> Vec<TileConnection> {
+    pub fn find_neighbors(&self, all_hashes: &[TileHash]) -> Vec<TileConnection> {
         let other_tiles = all_hashes.iter().filter(|t| t.id != self.id).collect::<Vec<_>>();
 
         // find all tiles that share a border with the given tile
@@ -46,7 +46,7 @@ impl TileHash {
                         let number_of_shared_borders = matching_borders.len();
 
                     if number_of_shared_borders > 1 {
-                        panic!(format!("Tile {} shares more than one border with tile {}", self.id, neighbor.id))
+                        panic!("Tile {} shares more than one border with tile {}", self.id, neighbor.id)
                     } else if number_of_shared_borders == 1 {
                         Some(matching_borders[0])
                     } else {
@@ -73,11 +73,11 @@ impl TileHash {
         connections
     }
 
-    fn print(&self) {
+    pub fn print(&self) {
         println!("Tile {}:", self.id);
         for h in &self.data {
             print!("{}  ", h);
         }
-        println!("");
+        println!();
     }
 }
@@ -1,41 +1,36 @@
 use std::collections::HashSet;
 use std::collections::HashMap;
 use std::str::FromStr;
-use regex::Regex;
-use super::utils::ParseError;
+use super::cancel;
+use super::utils::{parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(Debug)]
-struct Food {
-    ingredients: Vec<String>,
-    allergens: Vec<String>,
+pub struct Food{
+    pub ingredients: Vec<String>,
+    pub allergens: Vec<String>,
 }
 
 impl FromStr for Food{
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static!{
-            static ref RE: Regex = Regex::new(r"^(.*)\s\(contains\s(.*)\)$").unwrap();
-        }
-
-        let cap = RE.captures(s).ok_or(ParseError::new(&format!("Could not extract ingredients: {}", s)))?;
-        let ingredients = cap[1].split(" ").map(|v| v.to_owned()).collect::<Vec<_>>();
-        let allergens = cap[2].split(", ").map(|v| v.to_owned()).collect::<Vec<_>>();
+        let (ingredients, allergens) = scan!(s, "{} (contains {})", &str, &str)?;
+        let ingredients = ingredients.split(' ').map(|v| v.to_owned()).collect::<Vec<_>>();
+        let allergens = allergens.split(", ").map(|v| v.to_owned()).collect::<Vec<_>>();
 
         Ok(Self { ingredients, allergens })
     }
 }
 
-fn parse_input() -> Result<Vec<Food>, ParseError> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| Food::from_str(v))
-        .collect::<Result<Vec<_>, ParseError>>()
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<Food>, ParseError> {
+    parse_lines(input, Food::from_str)
 }
 
-fn map_allergens_to_food(food: &Vec<Food>) -> (HashMap<&str, HashSet<&str>>, HashSet<&str>) {
+pub fn map_allergens_to_food(food: &Vec<Food>) -> (HashMap<&str, HashSet<&str>>, HashSet<&str>) {
     let mut all_ingredients = HashSet::new();
     let mut map: HashMap<&str, Vec<HashSet<&str>>> = HashMap::new();
     for f in food {
@@ -43,7 +38,7 @@ fn map_allergens_to_food(food: &Vec<Food>) -> (HashMap<&str, HashSet<&str>>, Has
             for i in &f.ingredients {
                 all_ingredients.insert(i.as_str());
             }
-            map.entry(&a)
+            map.entry(a)
                 .and_modify(|l| l.push(f.ingredients.iter().map(|v| v.as_str()).collect::<HashSet<_>>()))
                 .or_insert(vec![f.ingredients.iter().map(|v| v.as_str()).collect::<HashSet<_>>()]);
         }
@@ -61,71 +56,102 @@ fn map_allergens_to_food(food: &Vec<Food>) -> (HashMap<&str, HashSet<&str>>, Has
     (allergen_to_food_map, all_ingredients)
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let food = parse_input()?;
+/// The ingredient left for an allergen once the others are ruled out.
+pub fn only_ingredient<'a>(allergen: &str, ingredients: &HashSet<&'a str>) -> Result<&'a str, Error> {
+    ingredients.iter().next()
+        .copied()
+        .ok_or(Error::solve(&format!("No ingredient is left that could contain {}", allergen)))
+}
 
-    let (allergen_to_food_map, all_ingredients) = map_allergens_to_food(&food);
+pub struct Day21;
 
-    let mut allergenic_ingredients = HashSet::new();
-    for (_, ingredients) in &allergen_to_food_map {
-        for ingredient in ingredients {
-            allergenic_ingredients.insert(ingredient);
-        }
-    }
+impl Solution for Day21 {
+    type Input = Vec<Food>;
 
-    let mut non_allergenic_ingredients = all_ingredients.clone();
-    for ingredient in allergenic_ingredients {
-        non_allergenic_ingredients.remove(ingredient);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
     }
 
-    let result = food.iter()
-        .map(|f| f.ingredients.iter().filter(|i| non_allergenic_ingredients.contains(i.as_str())))
-        .flatten()
-        .count();
+    fn part1(food: &Self::Input) -> Result<Answer, Error> {
+        let (allergen_to_food_map, all_ingredients) = map_allergens_to_food(food);
 
-    println!("21/1: # of times all non-allergenic food appears: {}", result);
+        let mut allergenic_ingredients = HashSet::new();
+        for ingredients in allergen_to_food_map.values() {
+            for ingredient in ingredients {
+                allergenic_ingredients.insert(ingredient);
+            }
+        }
 
-    Ok(())
-}
+        let mut non_allergenic_ingredients = all_ingredients.clone();
+        for ingredient in allergenic_ingredients {
+            non_allergenic_ingredients.remove(ingredient);
+        }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let food = parse_input()?;
-    let (allergen_to_food_map, _) = map_allergens_to_food(&food);
-
-    let mut a_to_i = allergen_to_food_map.iter()
-        .map(|(a, is)| (a, is.clone()))
-        .collect::<Vec<_>>();
-    a_to_i.sort_by(|a, b| a.1.len().cmp(&b.1.len()));
-
-    let mut already_assigned: HashSet<&str> = HashSet::new();
-    let mut max = 2;
-    while max > 1 {
-        for i in 0..a_to_i.len() {
-            if a_to_i[i].1.len() == 1 {
-                already_assigned.insert(a_to_i[i].1.iter().next().unwrap());
-                continue;
-            }
+        let result = food.iter()
+            .flat_map(|f| f.ingredients.iter().filter(|i| non_allergenic_ingredients.contains(i.as_str())))
+            .count();
 
-            for a in &already_assigned {
-                a_to_i[i].1.remove(a);
-            }
+        Ok(Answer::from(result))
+    }
 
-            if a_to_i[i].1.len() > 1 {
-                continue;
+    fn part2(food: &Self::Input) -> Result<Answer, Error> {
+        let (allergen_to_food_map, _) = map_allergens_to_food(food);
+
+        let mut a_to_i = allergen_to_food_map.iter()
+            .map(|(a, is)| (a, is.clone()))
+            .collect::<Vec<_>>();
+        a_to_i.sort_by_key(|a| a.1.len());
+
+        let mut already_assigned: HashSet<&str> = HashSet::new();
+        let mut max = 2;
+        while max > 1 {
+            cancel::checkpoint()?;
+            let assigned = already_assigned.len();
+            for (allergen, ingredients) in a_to_i.iter_mut() {
+                if ingredients.len() == 1 {
+                    already_assigned.insert(only_ingredient(allergen, ingredients)?);
+                    continue;
+                }
+
+                for a in &already_assigned {
+                    ingredients.remove(a);
+                }
+
+                if ingredients.len() > 1 {
+                    continue;
+                }
+
+                already_assigned.insert(only_ingredient(allergen, ingredients)?);
             }
 
-            already_assigned.insert(a_to_i[i].1.iter().next().unwrap());
+            max = a_to_i.iter().map(|v| v.1.len()).max()
+                .ok_or(Error::solve("No food lists any allergens"))?;
+
+            if max > 1 && already_assigned.len() == assigned {
+                return Err(Error::solve("Some allergens can't be assigned to a single ingredient"));
+            }
         }
 
-        max = a_to_i.iter().map(|v| v.1.len()).max().unwrap();
-    }
+        a_to_i.sort_by(|a, b| a.0.cmp(b.0));
+        let canonical_list = a_to_i.iter()
+            .map(|(allergen, ingredients)| only_ingredient(allergen, ingredients))
+            .collect::<Result<Vec<_>, Error>>()?
+            .join(",");
 
-    a_to_i.sort_by(|a, b| a.0.cmp(&b.0));
-    print!("21/2: Canonical dangerous ingredient list: ");
-    for i in a_to_i {
-        print!("{},", i.1.iter().next().unwrap());
+        Ok(Answer::from(canonical_list))
     }
-    println!("");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    Ok(())
+    #[test]
+    pub fn allergens_without_a_single_ingredient_are_rejected() {
+        let food = Day21::parse("a b (contains dairy)\nc d (contains dairy)\n").unwrap();
+        assert!(Day21::part2(&food).is_err());
+
+        let food = Day21::parse("a b (contains dairy)\n").unwrap();
+        assert!(Day21::part2(&food).is_err());
+    }
 }
@@ -1,39 +1,62 @@
 use std::collections::VecDeque;
-use super::utils::ParseError;
+use super::cancel;
+use super::progress;
+use super::utils::{parse, Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
-type Deck = VecDeque<u64>;
+pub type Deck = VecDeque<u64>;
 
 #[derive(Clone)]
-struct Game {
-    player: Vec<Deck>,
-    winner: Option<usize>,
+pub struct Game {
+    pub player: Vec<Deck>,
+    pub winner: Option<usize>,
 }
 
-fn parse_deck(s: &str) -> Deck {
-    s.lines()
-        .skip(1)
-        .map(|l| l.parse::<u64>().unwrap())
-        .collect()
+pub fn parse_deck(s: &str) -> Result<Deck, ParseError> {
+    let mut lines = Input::new(s).lines();
+
+    let header = lines.next().unwrap_or("");
+    if !header.starts_with("Player ") || !header.ends_with(':') {
+        return Err(ParseError::new("Expected a header like 'Player 1:'").with_text(header));
+    }
+
+    let deck = lines
+        .map(parse::<u64>)
+        .collect::<Result<Deck, _>>()?;
+
+    if deck.is_empty() {
+        return Err(ParseError::new("The deck is empty").with_text(header));
+    }
+
+    Ok(deck)
 }
 
-fn parse_input() -> Vec<Deck> {
-    let input = include_str!("./data/input.txt");
-    input
-        .split("\n\n")
-        .filter(|v| *v != "")
-        .map(|v| parse_deck(v))
-        .collect::<Vec<_>>()
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<Deck>, ParseError> {
+    let decks = Input::new(input)
+        .blocks()
+        .iter()
+        .map(|v| parse_deck(v.text()).map_err(|e| e.locate(input, v.text())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if decks.len() != 2 {
+        return Err(ParseError::new(&format!("Expected the decks of 2 players, found {}", decks.len())));
+    }
+
+    Ok(decks)
 }
 
-fn game_state_existed_before(previous_games: &Vec<Game>, current: &Game) -> bool {
+pub fn game_state_existed_before(previous_games: &[Game], current: &Game) -> bool {
     previous_games.iter()
         .any(|g| g.player[0] == current.player[0] && g.player[1] == current.player[1])
 }
 
-fn turn(mut game: Game, previous_turns: &mut Vec<Game>, recurse: bool, level: usize) -> Game {
-    if game_state_existed_before(&previous_turns, &game) {
+pub fn turn(mut game: Game, previous_turns: &[Game], recurse: bool, level: usize) -> Result<Game, Error> {
+    if game_state_existed_before(previous_turns, &game) {
         game.winner = Some(0);
-        return game;
+        return Ok(game);
     }
 
     let t1 = game.player[0].pop_front();
@@ -42,13 +65,13 @@ fn turn(mut game: Game, previous_turns: &mut Vec<Game>, recurse: bool, level: us
     if t1.is_none() {
         game.player[1].push_front(t2.unwrap());
         game.winner = Some(1);
-        return game;
+        return Ok(game);
     }
 
     if t2.is_none() {
         game.player[0].push_front(t1.unwrap());
         game.winner = Some(0);
-        return game;
+        return Ok(game);
     }
 
     let t1 = t1.unwrap();
@@ -60,7 +83,7 @@ fn turn(mut game: Game, previous_turns: &mut Vec<Game>, recurse: bool, level: us
         let mut ng = game.clone();
         ng.player[0] = ng.player[0].iter().take(t1 as usize).cloned().collect::<VecDeque<_>>();
         ng.player[1] = ng.player[1].iter().take(t2 as usize).cloned().collect::<VecDeque<_>>();
-        let rg = play_game(ng, recurse, level + 1);
+        let rg = play_game(ng, recurse, level + 1)?;
         winner = rg.winner.unwrap();
     } else {
         if t2 > t1 {
@@ -76,56 +99,69 @@ fn turn(mut game: Game, previous_turns: &mut Vec<Game>, recurse: bool, level: us
         game.player[1].push_back(t1);
     }
 
-    return game;
+    Ok(game)
 }
 
-fn play_game(mut game: Game, recurse: bool, level: usize) -> Game {
+pub fn play_game(mut game: Game, recurse: bool, level: usize) -> Result<Game, Error> {
+    // sub-games come and go too quickly to be worth reporting
+    let mut task = if level == 1 { Some(progress::task("Playing rounds", 0)) } else { None };
     let mut pg = vec![];
     loop {
-        let next_game = turn(game.clone(), &mut pg, recurse, level);
+        cancel::checkpoint()?;
+        if let Some(task) = &mut task {
+            task.inc(1);
+        }
+        let next_game = turn(game.clone(), &pg, recurse, level)?;
         pg.push(game);
         game = next_game;
         if game.winner.is_some() {
-            return game;
+            return Ok(game);
         }
     }
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let decks = parse_input();
-    let mut game = Game { player: decks, winner: None };
+pub struct Day22;
 
-    loop {
-        game = turn(game, &mut vec![], false, 1);
-        if game.winner.is_some() {
-            break;
-        }
-    }
+impl Solution for Day22 {
+    type Input = Vec<Deck>;
 
-    let winner = game.winner.unwrap();
-    let score: u64 = game.player[winner].iter().rev().enumerate()
-        .map(|(i, v)| (i + 1, v))
-        .map(|(i, v)| (i as u64) * v)
-        .sum();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    println!("22/1: score of winner's deck: {}", score);
+    fn part1(decks: &Self::Input) -> Result<Answer, Error> {
+        let mut game = Game { player: decks.clone(), winner: None };
+
+        let mut task = progress::task("Playing rounds", 0);
+        loop {
+            cancel::checkpoint()?;
+            task.inc(1);
+            game = turn(game, &[], false, 1)?;
+            if game.winner.is_some() {
+                break;
+            }
+        }
 
-    Ok(())
-}
+        let winner = game.winner.unwrap();
+        let score: u64 = game.player[winner].iter().rev().enumerate()
+            .map(|(i, v)| (i + 1, v))
+            .map(|(i, v)| (i as u64) * v)
+            .sum();
 
-pub fn problem2() -> Result<(), ParseError> {
-    let decks = parse_input();
-    let mut game = Game { player: decks, winner: None };
+        Ok(Answer::from(score))
+    }
 
-    game = play_game(game, true, 1);
+    fn part2(decks: &Self::Input) -> Result<Answer, Error> {
+        let mut game = Game { player: decks.clone(), winner: None };
 
-    let winner = game.winner.unwrap();
-    let score: u64 = game.player[winner].iter().rev().enumerate()
-        .map(|(i, v)| (i + 1, v))
-        .map(|(i, v)| (i as u64) * v)
-        .sum();
+        game = play_game(game, true, 1)?;
 
-    println!("22/2: score of winner's deck: {}", score);
+        let winner = game.winner.unwrap();
+        let score: u64 = game.player[winner].iter().rev().enumerate()
+            .map(|(i, v)| (i + 1, v))
+            .map(|(i, v)| (i as u64) * v)
+            .sum();
 
-    Ok(())
+        Ok(Answer::from(score))
+    }
 }
@@ -1,18 +1,37 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use itertools::join;
-use indicatif::ProgressBar;
-use super::utils::ParseError;
-
-fn get_input() -> Vec<usize> {
-    vec![4, 6, 3, 5, 2, 8, 1, 7, 9]
-}
+use super::cancel;
+use super::progress;
+use super::utils::{Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
+
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    let cups = Input::new(input)
+        .line()?
+        .chars()
+        .map(|c| c.to_digit(10)
+            .map(|d| d as usize)
+            .ok_or(ParseError::new(&format!("Not a cup label: '{}'", c))))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    // three cups are picked up each move, some have to be left to move them to
+    if cups.len() < 5 {
+        return Err(ParseError::new(&format!("Expected at least 5 cups, found {}", cups.len())));
+    }
+    let mut labels = cups.clone();
+    labels.sort_unstable();
+    if labels.into_iter().ne(1..=cups.len()) {
+        return Err(ParseError::new(&format!("Expected the cups to be labeled 1 to {}, each once", cups.len())));
+    }
 
-fn get_example() -> Vec<usize> {
-    vec![3, 8, 9, 1, 2, 5, 4, 6, 7]
+    Ok(cups)
 }
 
-fn input_to_map(input: &Vec<usize>) -> HashMap<usize, usize> {
+pub fn input_to_map(input: &[usize]) -> HashMap<usize, usize> {
     let mut map = HashMap::new();
     for i in 0..input.len()-1 {
         map.entry(input[i]).or_insert(input[i+1]);
@@ -21,14 +40,14 @@ fn input_to_map(input: &Vec<usize>) -> HashMap<usize, usize> {
     map
 }
 
-struct State {
-    current: usize,
-    max: usize,
-    map: HashMap<usize, usize>,
+pub struct State {
+    pub current: usize,
+    pub max: usize,
+    pub map: HashMap<usize, usize>,
 }
 
 impl State {
-    fn print(&self) {
+    pub fn print(&self) {
         let mut result = vec![];
 
         let mut current = self.current;
@@ -40,11 +59,11 @@ impl State {
     }
 }
 
-fn pick_three(state: &mut State) -> usize {
+pub fn pick_three(state: &mut State) -> usize {
     state.map[&state.current]
 }
 
-fn get_destination(max: usize, destination: usize) -> usize {
+pub fn get_destination(max: usize, destination: usize) -> usize {
     if destination == 1 {
         max
     } else {
@@ -52,7 +71,7 @@ fn get_destination(max: usize, destination: usize) -> usize {
     }
 }
 
-fn turn(state: &mut State) {
+pub fn turn(state: &mut State) {
     let three = pick_three(state);
 
     let first = three;
@@ -73,61 +92,85 @@ fn turn(state: &mut State) {
     state.current = state.map[&state.current];
 }
 
-fn rotate_to_1(cups: &mut VecDeque<u64>) {
+pub fn rotate_to_1(cups: &mut VecDeque<u64>) {
     while cups[0] != 1 {
         cups.rotate_left(1);
     }
 }
 
-fn checksum(state: &State) -> String {
+pub fn checksum(state: &State) -> String {
     let mut result = vec![];
     let mut previous = 1;
 
-    for _ in 1..9 {
+    for _ in 1..state.map.len() {
         previous = state.map[&previous];
         result.push(previous);
     }
     join(result, "")
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = get_input();
-    let cups = input_to_map(&input);
-    let mut state = State { current: input[0], max: 9, map: cups };
+pub struct Day23;
 
-    for _ in 0..100 {
-        turn(&mut state);
-    }
+impl Solution for Day23 {
+    type Input = Vec<usize>;
 
-    println!("23/1: order of cups starting with 1 except 1: {}", checksum(&state));
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    Ok(())
-}
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let cups = input_to_map(input);
+        let mut state = State { current: input[0], max: input.len(), map: cups };
 
-pub fn problem2() -> Result<(), ParseError> {
-    let mut cups = (1..=1_000_000).collect::<Vec<usize>>();
-    let first_10 = get_input();
+        for _ in 0..100 {
+            turn(&mut state);
+        }
 
-    for (i, v) in first_10.into_iter().enumerate() {
-        cups[i] = v;
+        Ok(Answer::from(checksum(&state)))
     }
 
-    let map = input_to_map(&cups);
-    let mut state = State { current: cups[0], max: 1_000_000, map };
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let mut cups = (1..=1_000_000).collect::<Vec<usize>>();
+        let first_10 = input.clone();
 
-    let iterations = 10_000_000;
-    let pb = ProgressBar::new(iterations);
-    for t in 0..iterations {
-        if t % 100_000 == 0 {
-            pb.inc(100_000);
+        for (i, v) in first_10.into_iter().enumerate() {
+            cups[i] = v;
         }
-        turn(&mut state);
+
+        let map = input_to_map(&cups);
+        let mut state = State { current: cups[0], max: 1_000_000, map };
+
+        let iterations = 10_000_000;
+        let mut task = progress::task("Moving cups", iterations);
+        for _ in 0..iterations {
+            task.inc(1);
+            cancel::checkpoint()?;
+            turn(&mut state);
+        }
+
+        let star1 = state.map[&1];
+        let star2 = state.map[&star1];
+        Ok(Answer::from(star1 * star2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn example_1_1() {
+        let input = Day23::parse(include_str!("./data/example.txt")).unwrap();
+        assert_eq!(Answer::from("67384529"), Day23::part1(&input).unwrap());
     }
-    pb.finish_and_clear();
 
-    let star1 = state.map[&1];
-    let star2 = state.map[&star1];
-    println!("23/2: product of the two cups the two stars are under: {}", star1 * star2);
+    #[test]
+    pub fn cups_have_to_be_labeled_one_to_n() {
+        assert!(Day23::parse("389").is_err());
+        assert!(Day23::parse("38902").is_err());
+        assert!(Day23::parse("38925").is_err());
 
-    Ok(())
+        let input = Day23::parse("32415").unwrap();
+        assert_eq!(4, Day23::part1(&input).unwrap().to_string().len());
+    }
 }
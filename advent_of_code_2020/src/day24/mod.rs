@@ -1,10 +1,13 @@
-use std::collections::HashMap;
 use std::ops::Add;
-use indicatif::ProgressBar;
-use super::utils::ParseError;
+use super::frames::{self, Frame};
+use super::progress;
+use super::utils::{parse_lines, Error, ParseError};
+use super::utils::grid::{Grid, Position, SparseGrid};
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(Debug)]
-enum Direction {
+pub enum Direction {
     NorthEast,
     East,
     SouthEast,
@@ -13,7 +16,17 @@ enum Direction {
     NorthWest,
 }
 
-fn parse_line(s: &str) -> Vec<Direction> {
+/// Every direction once, i.e. the way to each neighbor of a tile.
+pub const DIRECTIONS: [Direction; 6] = [
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+pub fn parse_line(s: &str) -> Result<Vec<Direction>, ParseError> {
     let mut i = s.chars().peekable();
     let mut result = vec![];
 
@@ -43,29 +56,32 @@ fn parse_line(s: &str) -> Vec<Direction> {
                 i.next();
                 Direction::NorthWest
             },
-            (v1, v2) => panic!(format!("Encountered unexpected pair of input chars: '{}' and '{:?}'", v1, v2))
+            (v1, v2) => {
+                let (text, message) = match v2 {
+                    Some(v2) => (format!("{}{}", v1, v2), format!("Encountered unexpected pair of input chars: '{}' and '{}'", v1, v2)),
+                    None => (v1.to_string(), format!("Encountered unexpected input char '{}' at the end of the line", v1)),
+                };
+                return Err(ParseError::new(&message).with_text(&text));
+            },
         };
 
         result.push(d);
     }
 
-    result
+    Ok(result)
 }
 
-fn parse_input() -> Vec<Vec<Direction>> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| parse_line(v))
-        .collect::<Vec<_>>()
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>, ParseError> {
+    parse_lines(input, parse_line)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coords {
-    x: i64,
-    y: i64,
-    z: i64,
+pub struct Coords {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
 }
 
 impl Coords {
@@ -107,111 +123,116 @@ impl Add for Coords {
     }
 }
 
-fn get_tile(instructions: &Vec<Direction>) -> Coords {
+pub fn get_tile(instructions: &[Direction]) -> Coords {
     let tile = instructions.iter()
-        .map(|d| Coords::from_direction(d))
+        .map(Coords::from_direction)
         .fold(Coords::zeroes(), |a, c| a + c);
 
     tile
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let input = parse_input();
-
-    let mut floor: HashMap<Coords, bool> = HashMap::new();
-    for tile in input.iter() {
-        let coords = get_tile(tile);
-        floor.entry(coords)
-            .and_modify(|v| *v = !*v)
-            .or_insert(true);
+impl Position for Coords {
+    fn neighbors(&self) -> Vec<Self> {
+        DIRECTIONS.iter()
+            .map(|d| *self + Coords::from_direction(d))
+            .collect()
     }
 
-    let result = floor.iter()
-        .filter(|(_, v)| **v)
-        .count();
-
-    println!("24/1: # of flipped tiles: {}", result);
+    fn min(&self, other: &Self) -> Self {
+        Coords::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
 
-    Ok(())
+    fn max(&self, other: &Self) -> Self {
+        Coords::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
 }
 
-// Create a floor big enough for part 2
-fn initialize_floor() -> HashMap<Coords, bool> {
-    let mut floor: HashMap<Coords, bool> = HashMap::new();
-
-    let directions = parse_line("neeseswwnw");
-    floor.entry(Coords::zeroes()).or_insert(false);
-    for _ in 0..125 {
-        let mut to_add = vec![];
-        for (c, _) in &floor {
-            for d in &directions {
-                let a = Coords::from_direction(&d);
-                to_add.push(*c + a);
-            }
-        }
-        for c in to_add {
-            floor.entry(c).or_insert(false);
-        }
+/// The tiles that are black, i.e. `true`.
+pub type Floor = SparseGrid<Coords, bool>;
+
+pub fn lay_floor(input: &[Vec<Direction>]) -> Floor {
+    let mut floor = Floor::new();
+    for tile in input.iter() {
+        let coords = get_tile(tile);
+        let black = floor.get(&coords) != Some(&true);
+        floor.insert(coords, black);
     }
 
     floor
 }
 
-fn count_black_neighbors(tile: &Coords, floor: &HashMap<Coords, bool>, ndirections: &Vec<Coords>) -> u64 {
-    let mut count = 0;
-    for d in ndirections {
-        if let Some(v) = floor.get(&(*tile + *d)) {
-            if *v {
-                count += 1;
-            }
+pub fn tick(floor: &Floor) -> Floor {
+    floor.life(|black, neighbors| neighbors == 2 || (black && neighbors == 1))
+}
+
+/// Draws the floor with the tiles of a row shifted by half a tile, i.e. a
+/// tile at `2x + y` in row `y`, black ones as `#`, white ones as `.`.
+pub fn draw_floor(floor: &Floor) -> Grid<char> {
+    let (min, max) = match floor.bounds() {
+        Some(bounds) => bounds,
+        None => return Grid::new(0, 0, ' '),
+    };
+    let left = 2 * min.x + min.y;
+    let width = (2 * max.x + max.y - left + 1) as usize;
+
+    Grid::from_fn(width, (max.y - min.y + 1) as usize, |(column, row)| {
+        let (column, y) = (left + column, min.y + row);
+        if (column - y).rem_euclid(2) != 0 {
+            return ' ';
         }
-    }
-    count
+
+        let x = (column - y).div_euclid(2);
+        match floor.get(&Coords::new(x, y, -x - y)) {
+            Some(true) => '#',
+            _ => '.',
+        }
+    })
 }
 
-fn tick(floor: HashMap<Coords, bool>) -> HashMap<Coords, bool> {
-    let mut new = floor.clone();
-    let ndirections = parse_line("neeseswwnw").into_iter()
-        .map(|d| Coords::from_direction(&d))
-        .collect::<Vec<_>>();
+pub struct Day24;
 
-    for (tile, black) in &floor {
-        let count = count_black_neighbors(&tile, &floor, &ndirections);
+impl Solution for Day24 {
+    type Input = Vec<Vec<Direction>>;
 
-        if *black && (count == 0 || count > 2) {
-            new.entry(*tile).and_modify(|v| *v = false);
-        }
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-        if !*black && count == 2 {
-            new.entry(*tile).and_modify(|v| *v = true);
-        }
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let floor = lay_floor(input);
+        frames::emit(|| Frame::new("day 0", draw_floor(&floor)))?;
+        let result = floor.count(|black| *black);
+
+        Ok(Answer::from(result))
     }
 
-    new
-}
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let mut floor = lay_floor(input);
 
-pub fn problem2() -> Result<(), ParseError> {
-    let input = parse_input();
+        let mut task = progress::task("Flipping tiles", 100);
+        frames::emit(|| Frame::new("day 0", draw_floor(&floor)))?;
+        for day in 1..=100 {
+            task.inc(1);
+            floor = tick(&floor);
+            frames::emit(|| Frame::new(&format!("day {}", day), draw_floor(&floor)))?;
+        }
 
-    let mut floor = initialize_floor();
-    for tile in input.iter() {
-        let coords = get_tile(tile);
-        floor.entry(coords)
-            .and_modify(|v| *v = !*v)
-            .or_insert(true);
-    }
+        let result = floor.count(|black| *black);
 
-    let pb = ProgressBar::new(100);
-    for _ in 0..100 {
-        pb.inc(1);
-        floor = tick(floor);
+        Ok(Answer::from(result))
     }
-    pb.finish_and_clear();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    let result = floor.iter()
-        .filter(|(_, v)| **v)
-        .count();
+    #[test]
+    pub fn unexpected_chars_are_named_in_the_error() {
+        let error = parse_line("enx").unwrap_err();
+        assert!(error.to_string().contains("'n' and 'x'"), "{}", error);
 
-    println!("24/2: # of flipped tiles: {}", result);
-    Ok(())
+        let error = parse_line("ees").unwrap_err();
+        assert!(error.to_string().contains("'s' at the end of the line"), "{}", error);
+    }
 }
@@ -1,48 +1,60 @@
-use super::utils::ParseError;
+use super::utils::{parse, parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
-fn get_pubkeys() -> (u64, u64) {
-    (8987316,  14681524)
-}
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let keys = parse_lines(input, parse::<u64>)?;
 
-fn get_example_keys() -> (u64, u64) {
-    (5764801, 17807724)
+    if keys.len() != 2 {
+        return Err(ParseError::new(&format!("Expected two public keys, found {}", keys.len())));
+    }
+
+    Ok((keys[0], keys[1]))
 }
 
-fn get_loop_size(pubkey: u64, subject_number: u64) -> u64 {
+pub fn get_loop_size(pubkey: u64, subject_number: u64) -> u64 {
     let mut v = 1;
     let mut ls = 0;
 
     while v != pubkey {
         v *= subject_number;
-        v = v % 20201227;
+        v %= 20201227;
         ls += 1;
     }
 
     ls
 }
 
-fn encrypt(key: u64, subject_number: u64) -> u64 {
+pub fn encrypt(key: u64, subject_number: u64) -> u64 {
     let mut v = 1;
 
     for _ in 0..key {
         v *= subject_number;
-        v = v % 20201227;
+        v %= 20201227;
     }
 
     v
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let (pub1, pub2) = get_pubkeys();
+pub struct Day25;
 
-    let ls1 = get_loop_size(pub1, 7);
-    let enc = encrypt(ls1, pub2);
+impl Solution for Day25 {
+    type Input = (u64, u64);
 
-    println!("25/1: encryption key is: {}", enc);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    Ok(())
-}
+    fn part1(&(pub1, pub2): &Self::Input) -> Result<Answer, Error> {
+        let ls1 = get_loop_size(pub1, 7);
+        let enc = encrypt(ls1, pub2);
+
+        Ok(Answer::from(enc))
+    }
 
-pub fn problem2() -> Result<(), ParseError> {
-    Ok(())
+    fn part2(_input: &Self::Input) -> Result<Answer, Error> {
+        Ok(Answer::None)
+    }
 }
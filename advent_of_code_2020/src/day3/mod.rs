@@ -1,70 +1,57 @@
-use super::utils::ParseError;
+use super::utils::{Error, Input, ParseError};
+use super::utils::grid::Grid;
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(PartialEq)]
-enum Floor {
+pub enum Floor {
     Tree,
     Free,
 }
 
-fn parse_line(line: &str) -> Vec<Floor> {
-    line.chars().map(|v| match v {
-        '#' => Floor::Tree,
-        _ => Floor::Free,
-    })
-    .collect::<Vec<_>>()
+pub fn parse_floor(c: char) -> Result<Floor, ParseError> {
+    match c {
+        '#' => Ok(Floor::Tree),
+        '.' => Ok(Floor::Free),
+        _ => Err(ParseError::new(&format!("Neither a tree nor free: '{}'", c))),
+    }
 }
 
-fn parse_input() -> Vec<Vec<Floor>> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(parse_line)
-        .collect::<Vec<_>>()
-}
+pub const INPUT: &str = include_str!("./data/input.txt");
 
-fn get_floor_at_position<'a>((index, line): (usize, &'a Vec<Floor>), slope: &'a(usize, usize)) -> Option<&'a Floor> {
-    line.iter()
-        .cycle()
-        .skip(index * slope.0 / slope.1)
-        .next()
-}
-
-fn is_tree(floor: &Option<&Floor>) -> bool {
-    if let Some(f) = floor {
-        f == &&Floor::Tree
-    } else {
-        false
-    }
+pub fn parse_input(input: &str) -> Result<Grid<Floor>, ParseError> {
+    Input::new(input).grid_with(parse_floor)
 }
 
-fn count_trees_on_path(map: &Vec<Vec<Floor>>, slope: &(usize, usize)) -> u64 {
-    map.iter()
-        .enumerate()
+pub fn count_trees_on_path(map: &Grid<Floor>, slope: &(usize, usize)) -> u64 {
+    // the map repeats to the right, but ends at the bottom
+    (0..map.height())
         .step_by(slope.1)
-        .map(|v| get_floor_at_position(v, slope))
-        .filter(|t| is_tree(t))
+        .filter(|y| map.get_wrapped(((y * slope.0 / slope.1) as i64, *y as i64)) == &Floor::Tree)
         .count() as u64
 }
 
-pub fn problem1() -> Result<u64, ParseError> {
-    let map = parse_input();
+pub struct Day3;
 
-    let number_of_trees = count_trees_on_path(&map, &(3, 1));
-    println!("3/1: # of trees: {}", number_of_trees);
+impl Solution for Day3 {
+    type Input = Grid<Floor>;
 
-    Ok(number_of_trees)
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-pub fn problem2() -> Result<u64, ParseError> {
-    let map = parse_input();
-    let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    fn part1(map: &Self::Input) -> Result<Answer, Error> {
+        let number_of_trees = count_trees_on_path(map, &(3, 1));
+        Ok(Answer::from(number_of_trees))
+    }
 
-    let result: u64 = slopes.iter()
-        .map(|slope| count_trees_on_path(&map, slope))
-        .product();
+    fn part2(map: &Self::Input) -> Result<Answer, Error> {
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
 
-    println!("3/2: product of # of trees on all slopes considered: {}", result);
+        let result: u64 = slopes.iter()
+            .map(|slope| count_trees_on_path(map, slope))
+            .product();
 
-    Ok(result)
+        Ok(Answer::from(result))
+    }
 }
@@ -1,7 +1,9 @@
-use super::utils::ParseError;
+use super::utils::{Error, Input, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 use regex::Regex;
 
-fn get_validator(key: &str) -> Box<dyn FieldValidator> {
+pub fn get_validator(key: &str) -> Box<dyn FieldValidator> {
     match key {
         "byr" => Box::new(YearValidator { min: 1920, max: 2002 }),
         "iyr" => Box::new(YearValidator { min: 2010, max: 2020 }),
@@ -15,33 +17,33 @@ fn get_validator(key: &str) -> Box<dyn FieldValidator> {
     }
 }
 
-struct Field<'a> {
-    identifier: &'a str,
-    value: &'a str,
+pub struct Field {
+    pub identifier: String,
+    pub value: String,
 }
 
-impl<'a> Field<'a> {
+impl Field {
     pub fn new(
-        identifier: &'a str,
-        value: &'a str) -> Self {
+        identifier: &str,
+        value: &str) -> Self {
 
-        Field { identifier, value }
+        Field { identifier: identifier.to_string(), value: value.to_string() }
     }
 
-    pub fn is_valid(&'a self) -> bool {
+    pub fn is_valid(&self) -> bool {
         // TODO: optimize this, we should not instantiate the validators every
         // time
-        let validator = get_validator(self.identifier);
+        let validator = get_validator(&self.identifier);
         validator.is_valid(self)
     }
 }
 
-trait FieldValidator {
+pub trait FieldValidator {
     fn is_valid(&self, field: &Field) -> bool;
 }
 
-struct EmptyValidator {
-    value: bool,
+pub struct EmptyValidator {
+    pub value: bool,
 }
 
 impl FieldValidator for EmptyValidator {
@@ -50,9 +52,9 @@ impl FieldValidator for EmptyValidator {
     }
 }
 
-struct YearValidator {
-    min: i16,
-    max: i16,
+pub struct YearValidator {
+    pub min: i16,
+    pub max: i16,
 }
 
 impl FieldValidator for YearValidator {
@@ -63,7 +65,7 @@ impl FieldValidator for YearValidator {
     }
 }
 
-struct HeightValidator {
+pub struct HeightValidator {
 }
 
 impl FieldValidator for HeightValidator {
@@ -79,49 +81,44 @@ impl FieldValidator for HeightValidator {
         RE.captures(&field.value)
             .map(|cap| {
                 let unit = &cap[2];
-                // TODO: Get rid of this unwrap
-                let size = cap[1].parse::<u32>().unwrap();
-
-                if unit == "cm" && size >= 150 && size <= 193 {
-                    true
-                } else if unit == "in" && size >= 59 && size <= 76 {
-                    true
-                } else {
-                    false
-                }
+                let size = match cap[1].parse::<u32>() {
+                    Ok(size) => size,
+                    Err(_) => return false,
+                };
+
+                (unit == "cm" && (150..=193).contains(&size)) || (unit == "in" && (59..=76).contains(&size))
             })
             .unwrap_or(false)
     }
 }
 
-struct PatternValidator {
-    regex: Regex,
+pub struct PatternValidator {
+    pub regex: Regex,
 }
 
 impl FieldValidator for PatternValidator {
     fn is_valid(&self, field: &Field) -> bool {
-        self.regex.is_match(field.value)
+        self.regex.is_match(&field.value)
     }
 }
 
-struct Passport<'a> {
-    fields: Vec<Field<'a>>,
+pub struct Passport {
+    pub fields: Vec<Field>,
 }
 
-fn parse_field<'a>(field: &'a str) -> Result<Field<'a>, ParseError> {
+pub fn parse_field(field: &str) -> Result<Field, ParseError> {
     let key_value = field.split(':').collect::<Vec<_>>();
 
     if key_value.len() != 2 {
-        Err(ParseError::new("foo"))
+        Err(ParseError::new("Expected a field of the form key:value").with_text(field))
     } else {
         Ok(Field::new(key_value[0], key_value[1]))
     }
 }
 
-impl<'a> Passport<'a> {
-    fn from_str(line: &'a str) -> Result<Passport<'a>, ParseError> {
-        let fields = line.split(|c| c == ' ' || c == '\n')
-            .filter(|v| !v.is_empty())
+impl Passport {
+    pub fn parse(line: &str) -> Result<Passport, ParseError> {
+        let fields = line.split_whitespace()
             .map(parse_field)
             .collect::<Result<Vec<_>, ParseError>>()?;
 
@@ -129,53 +126,56 @@ impl<'a> Passport<'a> {
     }
 }
 
-impl<'a> Passport<'a> {
-    fn has_required_fields(&self) -> bool {
-        let required_fields = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+impl Passport {
+    pub fn has_required_fields(&self) -> bool {
+        let required_fields = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
 
         required_fields.iter()
             .all(|&k| self.fields.iter().any(|f| f.identifier == k))
     }
 
-    fn is_valid(&self) -> bool {
+    pub fn is_valid(&self) -> bool {
         self.fields
             .iter()
             .all(|v| v.is_valid())
     }
 }
 
-fn parse_input<'a>() -> Result<Vec<Passport<'a>>, ParseError> {
-    let input = include_str!("./data/input.txt");
+pub const INPUT: &str = include_str!("./data/input.txt");
 
-    input.split("\n\n")
-        .filter(|v| !v.is_empty())
-        .map(|v| Passport::from_str(v))
+pub fn parse_input(input: &str) -> Result<Vec<Passport>, ParseError> {
+    Input::new(input)
+        .blocks()
+        .iter()
+        .map(|v| Passport::parse(v.text()).map_err(|e| e.locate(input, v.text())))
         .collect::<Result<Vec<_>, ParseError>>()
 }
 
-pub fn problem1() -> Result<usize, ParseError> {
-    let input = parse_input()?;
-
-    let solution = input.iter()
-        .filter(|p| p.has_required_fields())
-        .count();
+pub struct Day4;
 
-    println!("4/1: # of 'valid' passports: {}", solution);
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
 
-    Ok(solution)
-}
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-pub fn problem2() -> Result<usize, ParseError> {
-    let input = parse_input()?;
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let solution = input.iter()
+            .filter(|p| p.has_required_fields())
+            .count();
 
-    let solution = input.iter()
-        .filter(|p| p.has_required_fields())
-        .filter(|p| p.is_valid())
-        .count();
+        Ok(Answer::from(solution))
+    }
 
-    println!("4/2: # of 'valid' passports: {}", solution);
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let solution = input.iter()
+            .filter(|p| p.has_required_fields())
+            .filter(|p| p.is_valid())
+            .count();
 
-    Ok(solution)
+        Ok(Answer::from(solution))
+    }
 }
 
 #[cfg(test)]
@@ -186,13 +186,13 @@ mod test {
     pub fn valid_expiration_year_can_be_validated() {
         let field = Field::new("eyr", "2020");
 
-        assert_eq!(true, field.is_valid());
+        assert!(field.is_valid());
     }
 
     #[test]
     pub fn invalid_expiration_year_can_be_validated() {
         let field = Field::new("eyr", "2015");
 
-        assert_eq!(false, field.is_valid());
+        assert!(!field.is_valid());
     }
 }
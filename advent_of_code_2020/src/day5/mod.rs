@@ -1,12 +1,14 @@
 use itertools::Itertools;
-use super::utils::ParseError;
+use super::utils::{Error, Input};
+use super::answer::Answer;
+use super::solution::Solution;
 
-enum Direction {
+pub enum Direction {
     Up,
     Down,
 }
 
-fn char_to_direction(c: char) -> Direction {
+pub fn char_to_direction(c: char) -> Direction {
     match c {
         'F' => Direction::Down,
         'L' => Direction::Down,
@@ -14,18 +16,18 @@ fn char_to_direction(c: char) -> Direction {
     }
 }
 
-fn parse_input() -> Vec<Vec<Direction>> {
-    let input = include_str!("./data/input.txt");
-    input
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Vec<Vec<Direction>> {
+    Input::new(input)
         .lines()
-        .filter(|v| *v != "")
         .map(|v| v.chars()
-            .map(|c| char_to_direction(c))
+            .map(char_to_direction)
             .collect::<Vec<_>>())
         .collect::<Vec<_>>()
 }
 
-fn binary_search(partition_steps: &Vec<&Direction>) -> usize {
+pub fn binary_search(partition_steps: &Vec<&Direction>) -> usize {
     let bits = partition_steps.len();
     let base: usize = 2;
     let initial_range = (0, base.pow(bits as u32));
@@ -40,7 +42,7 @@ fn binary_search(partition_steps: &Vec<&Direction>) -> usize {
         }).0
 }
 
-fn seat(pass: &Vec<Direction>) -> (usize, usize) {
+pub fn seat(pass: &[Direction]) -> (usize, usize) {
     let row_encoded = pass.iter().take(7).collect::<Vec<_>>();
     let row = binary_search(&row_encoded);
 
@@ -50,44 +52,46 @@ fn seat(pass: &Vec<Direction>) -> (usize, usize) {
     (row, col)
 }
 
-fn seat_id(seat: (usize, usize)) -> usize {
+pub fn seat_id(seat: (usize, usize)) -> usize {
     seat.0 * 8 + seat.1
 }
 
-pub fn problem1() -> Result<usize, ParseError> {
-    let passes = parse_input();
-
-    let mut seat_ids = passes.iter()
-        .map(|p| seat(p))
-        .map(|p| seat_id(p))
-        .collect::<Vec<usize>>();
-    seat_ids.sort_unstable();
-
-    let highest_seat_id = seat_ids.iter().last().ok_or(ParseError::new("something went wrong"))?;
-
-    println!("5/1: Highest seat id already taken: {}", highest_seat_id);
+pub struct Day5;
 
-    Ok(*highest_seat_id)
-}
+impl Solution for Day5 {
+    type Input = Vec<Vec<Direction>>;
 
-pub fn problem2() -> Result<usize, ParseError> {
-    let input = parse_input();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input))
+    }
 
-    let mut seat_ids = input.iter()
-        .map(|p| seat(p))
-        .map(|p| seat_id(p))
-        .collect::<Vec<_>>();
-    seat_ids.sort_unstable();
+    fn part1(passes: &Self::Input) -> Result<Answer, Error> {
+        let mut seat_ids = passes.iter()
+            .map(|p| seat(p))
+            .map(seat_id)
+            .collect::<Vec<usize>>();
+        seat_ids.sort_unstable();
 
-    let free_seats = seat_ids.iter()
-        .tuple_windows::<(_, _)>()
-        .map(|(&seat, &next_seat)| (next_seat - seat, seat))
-        .filter(|(space, _)| *space == 2)
-        .map(|(_, free_seat)| free_seat + 1)
-        .collect::<Vec<_>>();
+        let highest_seat_id = seat_ids.iter().last().ok_or(Error::solve("something went wrong"))?;
 
-    let my_seat = free_seats.iter().next().ok_or(ParseError::new("something went wrong"))?;
-    println!("5/2: My seat id: {}", my_seat);
+        Ok(Answer::from(*highest_seat_id))
+    }
 
-    Ok(*my_seat)
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let mut seat_ids = input.iter()
+            .map(|p| seat(p))
+            .map(seat_id)
+            .collect::<Vec<_>>();
+        seat_ids.sort_unstable();
+
+        let free_seats = seat_ids.iter()
+            .tuple_windows::<(_, _)>()
+            .map(|(&seat, &next_seat)| (next_seat - seat, seat))
+            .filter(|(space, _)| *space == 2)
+            .map(|(_, free_seat)| free_seat + 1)
+            .collect::<Vec<_>>();
+
+        let my_seat = free_seats.first().ok_or(Error::solve("something went wrong"))?;
+        Ok(Answer::from(*my_seat))
+    }
 }
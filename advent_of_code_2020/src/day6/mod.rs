@@ -1,28 +1,31 @@
 use std::collections::HashSet;
-use super::utils::ParseError;
+use super::utils::{Error, Input};
+use super::answer::Answer;
+use super::solution::Solution;
 
-fn parse_input() -> Vec<Vec<&'static str>> {
-    let input = include_str!("./data/input.txt");
-    input
-        .split("\n\n")
-        .filter(|v| *v != "")
-        .map(|g| g.lines().collect::<Vec<_>>())
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Vec<Vec<String>> {
+    Input::new(input)
+        .blocks()
+        .iter()
+        .map(|g| g.lines().map(|l| l.to_string()).collect::<Vec<_>>())
         .collect::<Vec<_>>()
 }
 
-fn answers_as_set(answers: &str) -> HashSet<char> {
+pub fn answers_as_set(answers: &str) -> HashSet<char> {
     answers.chars().collect()
 }
 
-fn union(a: HashSet<char>, b: HashSet<char>) -> HashSet<char> {
+pub fn union(a: HashSet<char>, b: HashSet<char>) -> HashSet<char> {
     a.union(&b).cloned().collect()
 }
 
-fn intersection(a: HashSet<char>, b: HashSet<char>) -> HashSet<char> {
+pub fn intersection(a: HashSet<char>, b: HashSet<char>) -> HashSet<char> {
     a.intersection(&b).cloned().collect()
 }
 
-fn reduce_answers<F>(group: &Vec<&str>, set_operation: F) -> Option<usize>
+pub fn reduce_answers<F>(group: &[String], set_operation: F) -> Option<usize>
     where
         F: FnMut(HashSet<char>, HashSet<char>) -> HashSet<char> {
     let mut answers = group.iter()
@@ -41,34 +44,32 @@ fn reduce_answers<F>(group: &Vec<&str>, set_operation: F) -> Option<usize>
         .map(|set| set.len())
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let groups = parse_input();
+pub struct Day6;
 
-    let number_of_identical_answers: Option<usize> = groups.iter()
-        .map(|g| reduce_answers(g, union))
-        .sum();
+impl Solution for Day6 {
+    type Input = Vec<Vec<String>>;
 
-    if let Some(v) = number_of_identical_answers{
-        println!("6/1: # of answers: {}", v);
-    } else {
-        println!("6/1: Something went wrong.");
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input))
     }
 
-    Ok(())
-}
+    fn part1(groups: &Self::Input) -> Result<Answer, Error> {
+        let number_of_identical_answers: Option<usize> = groups.iter()
+            .map(|g| reduce_answers(g, union))
+            .sum();
 
-pub fn problem2() -> Result<(), ParseError> {
-    let groups = parse_input();
+        number_of_identical_answers
+            .map(Answer::from)
+            .ok_or(Error::solve("Something went wrong."))
+    }
 
-    let number_of_answers: Option<usize> = groups.iter()
-        .map(|g| reduce_answers(g, intersection))
-        .sum();
+    fn part2(groups: &Self::Input) -> Result<Answer, Error> {
+        let number_of_answers: Option<usize> = groups.iter()
+            .map(|g| reduce_answers(g, intersection))
+            .sum();
 
-    if let Some(v) = number_of_answers{
-        println!("6/2: # of identical answers: {}", v);
-    } else {
-        println!("6/2: Something went wrong.");
+        number_of_answers
+            .map(Answer::from)
+            .ok_or(Error::solve("Something went wrong."))
     }
-
-    Ok(())
 }
@@ -1,9 +1,10 @@
-use regex::Regex;
 use std::str::FromStr;
-use super::utils::ParseError;
+use super::utils::{parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
 
 #[derive(Debug, Copy, Clone)]
-enum Instruction {
+pub enum Instruction {
     Acc(i32),
     Jmp(i32),
     Nop(i32),
@@ -13,35 +14,24 @@ impl FromStr for Instruction {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static!{
-            static ref RE: Regex = Regex::new(r"^(\w+)\s([\+-]\d+)$").unwrap();
-        }
-
-        let cap = RE.captures(s).ok_or(ParseError::new(&format!("Could not parse instruction '{}'.", s)))?;
+        let (op, param) = scan!(s, "{} {}", &str, i32)?;
 
-        // todo: resolve double Result
-        let param = cap.get(2).map(|v| v.as_str().parse::<i32>()).ok_or(ParseError::new(&format!("Could not parse parameter of instruction '{}'.", s)))??;
-        match cap.get(1).map(|m| m.as_str()).ok_or(ParseError::new(&format!("Could not parse instruction operator '{}'.", s)))? {
+        match op {
             "acc" => Ok(Instruction::Acc(param)),
             "jmp" => Ok(Instruction::Jmp(param)),
             "nop" => Ok(Instruction::Nop(param)),
-            _ => Err(ParseError::new(&format!("Invalid instruction '{}'", s)))
+            op => Err(ParseError::new(&format!("Invalid instruction '{}'", s)).with_text(op))
         }
     }
 }
 
-fn parse_input() -> Result<Vec<Instruction>, ParseError> {
-    let input = include_str!("./data/input.txt");
-    let instructions = input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| Instruction::from_str(v))
-        .collect::<Result<Vec<_>, ParseError>>()?;
+pub const INPUT: &str = include_str!("./data/input.txt");
 
-    Ok(instructions)
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, Instruction::from_str)
 }
 
-fn run(instructions: Vec<Instruction>) -> (i32, bool) {
+pub fn run(instructions: Vec<Instruction>) -> (i32, bool) {
     let mut ip: i32 = 0;
     let mut acc: i32 = 0;
     let mut instructions_with_visitation = instructions.into_iter()
@@ -66,16 +56,7 @@ fn run(instructions: Vec<Instruction>) -> (i32, bool) {
     (acc, looped)
 }
 
-pub fn problem1() -> Result<(), ParseError> {
-    let instructions = parse_input()?;
-
-    let (acc, _) = run(instructions);
-    println!("8/1: Value of the accumulator before looping: {}", acc);
-
-    Ok(())
-}
-
-fn flip(instruction: Instruction) -> Instruction {
+pub fn flip(instruction: Instruction) -> Instruction {
     match instruction {
         Instruction::Acc(_) => instruction,
         Instruction::Jmp(v) => Instruction::Nop(v),
@@ -83,22 +64,34 @@ fn flip(instruction: Instruction) -> Instruction {
     }
 }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let instructions = parse_input()?;
-    let mut acc = 0;
+pub struct Day8;
 
-    for i in 0..instructions.len() {
-        let mut copy = instructions.clone();
-        copy[i] = flip(copy[i]);
+impl Solution for Day8 {
+    type Input = Vec<Instruction>;
 
-        let (result, looped) = run(copy);
-        if !looped {
-            acc = result;
-            break;
-        }
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
     }
 
-    println!("8/2: Value of the accumulator when not looping: {}", acc);
+    fn part1(instructions: &Self::Input) -> Result<Answer, Error> {
+        let (acc, _) = run(instructions.clone());
+        Ok(Answer::from(acc))
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Answer, Error> {
+        let mut acc = 0;
+
+        for i in 0..instructions.len() {
+            let mut copy = instructions.clone();
+            copy[i] = flip(copy[i]);
 
-    Ok(())
+            let (result, looped) = run(copy);
+            if !looped {
+                acc = result;
+                break;
+            }
+        }
+
+        Ok(Answer::from(acc))
+    }
 }
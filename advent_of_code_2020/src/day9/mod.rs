@@ -1,22 +1,25 @@
 use itertools::Itertools;
-use super::utils::ParseError;
-
-fn parse_input() -> Vec<u64> {
-    let input = include_str!("./data/input.txt");
-    input
-        .lines()
-        .filter(|v| *v != "")
-        .map(|v| v.parse::<u64>().unwrap())
-        .collect::<Vec<_>>()
+use super::utils::{parse, parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
+
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse_lines(input, parse::<u64>)
 }
 
-fn is_valid(number: u64, preamble: &[u64]) -> bool {
+pub fn is_valid(number: u64, preamble: &[u64]) -> bool {
     preamble.iter()
         .combinations(2)
         .any(|v| v[0] + v[1] == number)
 }
 
-fn find_invalid_number(input: &Vec<u64>, preamble_length: usize) -> (u64, usize) {
+pub fn find_invalid_number(input: &[u64], preamble_length: usize) -> Result<(u64, usize), Error> {
+    if input.len() <= preamble_length {
+        return Err(Error::solve(&format!("Expected more than {} numbers, found {}", preamble_length, input.len())));
+    }
+
     let result = input.iter()
         .enumerate()
         .skip(preamble_length)
@@ -25,29 +28,20 @@ fn find_invalid_number(input: &Vec<u64>, preamble_length: usize) -> (u64, usize)
         .collect::<Vec<_>>();
 
     if result.len() != 1 {
-        println!("Unexpected number of results: {}", result.len());
+        return Err(Error::solve(&format!("Expected one invalid number, found {}", result.len())));
     }
 
-    (*result[0].0, result[0].1)
+    Ok((*result[0].0, result[0].1))
 }
 
-pub fn problem1() -> Result<(u64, usize), ParseError> {
-    let input = parse_input();
-
-    let result = find_invalid_number(&input, 25);
-    println!("9/1: invalid number: {}", result.0);
-
-    Ok(result)
-}
-
-fn checksum(v: &[u64]) -> u64 {
+pub fn checksum(v: &[u64]) -> u64 {
     let max = v.iter().max().unwrap();
     let min = v.iter().min().unwrap();
 
     *max + *min
 }
 
-fn check_window_size(relevant_numbers: &[u64], window_size: usize, invalid_number: u64) -> Option<u64> {
+pub fn check_window_size(relevant_numbers: &[u64], window_size: usize, invalid_number: u64) -> Option<u64> {
     // TODO: early exit?
     let sum: Vec<(u64, u64)> = relevant_numbers
         .windows(window_size)
@@ -65,20 +59,49 @@ fn check_window_size(relevant_numbers: &[u64], window_size: usize, invalid_numbe
     }
 }
 
-pub fn problem2() -> Result<(), ParseError> {
-    let input = parse_input();
-    let (invalid_number, position) = find_invalid_number(&input, 25);
+pub struct Day9;
 
-    let relevant_numbers = &input[0..position];
+impl Solution for Day9 {
+    type Input = Vec<u64>;
 
-    let result = (2..relevant_numbers.len()/2)
-        .find_map(|window| check_window_size(relevant_numbers, window, invalid_number));
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
 
-    if let Some(checksum) = result {
-        println!("9/2: encryption weakness: {}", checksum);
-    } else {
-        println!("Found nothing :(");
+    fn part1(input: &Self::Input) -> Result<Answer, Error> {
+        let result = find_invalid_number(input, 25)?;
+        Ok(Answer::from(result.0))
     }
 
-    Ok(())
-}
\ No newline at end of file
+    fn part2(input: &Self::Input) -> Result<Answer, Error> {
+        let (invalid_number, position) = find_invalid_number(input, 25)?;
+
+        let relevant_numbers = input.get(..position)
+            .ok_or(Error::solve("The invalid number is not part of the input"))?;
+
+        let result = (2..relevant_numbers.len()/2)
+            .find_map(|window| check_window_size(relevant_numbers, window, invalid_number));
+
+        result
+            .map(Answer::from)
+            .ok_or(Error::solve("Found nothing :("))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn inputs_without_one_invalid_number_are_rejected() {
+        let numbers = (1..=30).map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let short = Day9::parse(&numbers[..20].join("\n")).unwrap();
+        assert!(Day9::part1(&short).is_err());
+        assert!(Day9::part2(&short).is_err());
+
+        // every number after the preamble is the sum of two before it
+        let valid = Day9::parse(&numbers.join("\n")).unwrap();
+        assert!(Day9::part1(&valid).is_err());
+    }
+}
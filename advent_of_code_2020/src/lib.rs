@@ -0,0 +1,97 @@
+//! Solutions for Advent of Code 2020.
+//!
+//! Every day lives in its own module `dayN` with a parser for its input, its
+//! domain types and a `Solution` for both parts. `register` adds all of them
+//! to the registry the `aoc` binary runs.
+
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+extern crate num;
+extern crate dynparser;
+#[macro_use]
+extern crate aoc_core;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+// aoc new adds new days above this line
+
+// the days use the shared modules as if they were their own
+pub use aoc_core::{answer, cancel, frames, progress, solution, utils};
+use aoc_core::registry::{Registry, Year};
+
+pub const YEAR: u16 = 2020;
+
+/// Adds all solutions of 2020 to `registry`.
+pub fn register(registry: &mut Registry) {
+    registry.add_year(Year {
+        year: YEAR,
+        src: concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+        answers: concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt"),
+    });
+
+    registry.register::<day1::Day1>(YEAR, 1, day1::INPUT);
+    registry.register::<day2::Day2>(YEAR, 2, day2::INPUT);
+    registry.register::<day3::Day3>(YEAR, 3, day3::INPUT);
+    registry.register::<day4::Day4>(YEAR, 4, day4::INPUT);
+    registry.register::<day5::Day5>(YEAR, 5, day5::INPUT);
+    registry.register::<day6::Day6>(YEAR, 6, day6::INPUT);
+    registry.register::<day7::Day7>(YEAR, 7, day7::INPUT);
+    registry.register::<day8::Day8>(YEAR, 8, day8::INPUT);
+    registry.register::<day9::Day9>(YEAR, 9, day9::INPUT);
+    registry.register::<day10::Day10>(YEAR, 10, day10::INPUT);
+    registry.register::<day11::Day11>(YEAR, 11, day11::INPUT);
+    registry.register::<day12::Day12>(YEAR, 12, day12::INPUT);
+    registry.register::<day13::Day13>(YEAR, 13, day13::INPUT);
+    registry.register::<day14::Day14>(YEAR, 14, day14::INPUT);
+    registry.register::<day15::Day15>(YEAR, 15, day15::INPUT);
+    registry.register::<day16::Day16>(YEAR, 16, day16::INPUT);
+    registry.register::<day17::Day17>(YEAR, 17, day17::INPUT);
+    registry.register::<day18::Day18>(YEAR, 18, day18::INPUT);
+    registry.register::<day19::Day19>(YEAR, 19, day19::INPUT);
+    registry.register::<day20::Day20>(YEAR, 20, day20::INPUT);
+    registry.register::<day21::Day21>(YEAR, 21, day21::INPUT);
+    registry.register::<day22::Day22>(YEAR, 22, day22::INPUT);
+    registry.register::<day23::Day23>(YEAR, 23, day23::INPUT);
+    registry.register::<day24::Day24>(YEAR, 24, day24::INPUT);
+    registry.register::<day25::Day25>(YEAR, 25, day25::INPUT);
+    // aoc new adds new days above this line
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn all_days_are_registered() {
+        let mut registry = Registry::new();
+        register(&mut registry);
+
+        let days = registry.days(YEAR).map(|e| e.day).collect::<Vec<_>>();
+        assert_eq!((1..=25).collect::<Vec<_>>(), days);
+        assert_eq!(Some(YEAR), registry.latest_year());
+    }
+}
@@ -0,0 +1,19 @@
+extern crate advent2020;
+extern crate aoc_core;
+
+use advent2020::YEAR;
+use aoc_core::registry::Registry;
+
+/// Solves `part` of `input` with the registered day and compares the answer
+/// to the expected one.
+fn check(day: u8, part: u8, input: &str, expected: &str) {
+    let mut registry = Registry::new();
+    advent2020::register(&mut registry);
+    let entry = registry.get(YEAR, day).expect("Day is registered");
+
+    let input = entry.puzzle.parse(input).unwrap();
+    assert_eq!(expected, entry.puzzle.solve(part, input.as_ref()).unwrap().to_string());
+}
+
+// one test per example file and part, see build.rs
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,72 @@
+extern crate advent2020;
+extern crate aoc_core;
+
+use advent2020::answer::Answer;
+use advent2020::day8::{self, Day8};
+use advent2020::day18;
+use advent2020::day24::{self, Coords};
+use advent2020::solution::Solution;
+use advent2020::YEAR;
+use aoc_core::registry::Registry;
+
+const DAY8_EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+#[test]
+pub fn day8_interpreter_detects_the_loop() {
+    let instructions = day8::parse_input(DAY8_EXAMPLE).unwrap();
+
+    assert_eq!((5, true), day8::run(instructions));
+}
+
+#[test]
+pub fn day8_solution_can_be_used_directly() {
+    let input = Day8::parse(DAY8_EXAMPLE).unwrap();
+
+    assert_eq!(Answer::from(5), Day8::part1(&input).unwrap());
+    assert_eq!(Answer::from(8), Day8::part2(&input).unwrap());
+}
+
+#[test]
+pub fn day18_evaluators_differ_in_precedence() {
+    let expression = "2 * 3 + (4 * 5)";
+
+    assert_eq!(26, day18::parse(expression, 0, &day18::evaluate).1);
+    assert_eq!(46, day18::parse(expression, 0, &day18::evaluate_with_precedence).1);
+}
+
+#[test]
+pub fn day24_directions_walk_hex_coordinates() {
+    let directions = day24::parse_line("nwwswee").unwrap();
+
+    assert_eq!(Coords::zeroes(), day24::get_tile(&directions));
+}
+
+#[test]
+pub fn registered_days_solve_their_bundled_input() {
+    let mut registry = Registry::new();
+    advent2020::register(&mut registry);
+    let entry = registry.get(YEAR, 1).unwrap();
+
+    let input = entry.puzzle.parse(entry.input).unwrap();
+    assert_eq!(Answer::from(719796), entry.puzzle.solve(1, input.as_ref()).unwrap());
+}
+
+#[test]
+pub fn inputs_saved_on_windows_solve_the_same() {
+    let mut registry = Registry::new();
+    advent2020::register(&mut registry);
+
+    // days with blank line separated sections, a grid and a single line
+    for &day in &[4, 6, 11, 16, 19, 20, 22, 23] {
+        let entry = registry.get(YEAR, day).unwrap();
+        let windows = entry.input.replace('\n', " \r\n");
+
+        let expected = entry.puzzle.parse(entry.input).unwrap();
+        let actual = entry.puzzle.parse(&windows).unwrap();
+        assert_eq!(
+            entry.puzzle.solve(1, expected.as_ref()).unwrap(),
+            entry.puzzle.solve(1, actual.as_ref()).unwrap(),
+            "day {}", day,
+        );
+    }
+}
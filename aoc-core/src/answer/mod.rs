@@ -0,0 +1,115 @@
+use std::convert::TryFrom;
+use std::fmt;
+use num::BigInt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    /// Integers that don't fit into an i64
+    BigInteger(BigInt),
+    Text(String),
+    /// Answers that have to be looked at, e.g. letters drawn on a grid
+    Grid(Vec<String>),
+    /// Parts without a puzzle, e.g. the second part of day 25
+    None,
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{}", v),
+            Answer::BigInteger(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+            Answer::Grid(lines) => write!(f, "{}", lines.join("\n")),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::Integer(v as i64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    match i64::try_from(v) {
+                        Ok(v) => Answer::Integer(v),
+                        Err(_) => Answer::BigInteger(BigInt::from(v)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_signed!(i8, i16, i32, i64);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(v: BigInt) -> Self {
+        Answer::BigInteger(v)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Answer::Text(v.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Grid(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn small_unsigned_integers_are_integers() {
+        assert_eq!(Answer::Integer(42), Answer::from(42usize));
+        assert_eq!(Answer::Integer(42), Answer::from(42u64));
+    }
+
+    #[test]
+    pub fn large_unsigned_integers_are_big_integers() {
+        let answer = Answer::from(u64::MAX);
+
+        assert_eq!(Answer::BigInteger(BigInt::from(u64::MAX)), answer);
+        assert_eq!("18446744073709551615", answer.to_string());
+    }
+
+    #[test]
+    pub fn grids_are_displayed_line_by_line() {
+        let answer = Answer::from(vec!["#..".to_string(), ".#.".to_string()]);
+
+        assert!(answer.is_multiline());
+        assert_eq!("#..\n.#.", answer.to_string());
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+use std::fs;
+use std::time::{Duration, Instant};
+use super::memory::{self, Usage};
+use super::registry::Entry;
+use super::utils::Error;
+
+/// The phases of a day that are timed separately.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Phase {
+    fn from_name(name: &str) -> Option<Phase> {
+        match name {
+            "parse" => Some(Phase::Parse),
+            "part1" => Some(Phase::Part(1)),
+            "part2" => Some(Phase::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(p) => write!(f, "part{}", p),
+        }
+    }
+}
+
+/// Summary of the wall times of all runs of a phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+
+        Stats {
+            min: samples[0],
+            median: percentile(samples, 50),
+            p95: percentile(samples, 95),
+        }
+    }
+}
+
+/// Nearest rank percentile of sorted samples.
+fn percentile(samples: &[Duration], p: usize) -> Duration {
+    let rank = (p * samples.len()).div_ceil(100);
+    samples[rank.max(1) - 1]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+    /// What the first run allocated, if the binary counts allocations. It's
+    /// not saved, as it doesn't change from one run to the next.
+    pub memory: Option<Usage>,
+}
+
+/// Parses the input and solves the given parts `runs` times each.
+pub fn bench_day(entry: &Entry, input: &str, parts: &[u8], runs: usize) -> Result<Vec<Measurement>, Error> {
+    let mut measurements = Vec::new();
+
+    let mut samples = Vec::with_capacity(runs);
+    let mut parsed = None;
+    let mut usage = None;
+    for run in 0..runs {
+        let start = Instant::now();
+        let (result, memory) = memory::measure(|| entry.puzzle.parse(input));
+        let result = result
+            .map_err(|e| Error::new(&format!("{}: Unable to parse input: {}", entry.day, e)))?;
+        samples.push(start.elapsed());
+        parsed = Some(result);
+        if run == 0 {
+            usage = memory;
+        }
+    }
+    let parsed = parsed.ok_or(Error::new("At least one run is needed"))?;
+    measurements.push(Measurement { day: entry.day, phase: Phase::Parse, stats: Stats::from_samples(&mut samples), memory: usage });
+
+    for part in parts {
+        let mut samples = Vec::with_capacity(runs);
+        let mut usage = None;
+        for run in 0..runs {
+            let start = Instant::now();
+            let (result, memory) = memory::measure(|| entry.puzzle.solve(*part, parsed.as_ref()));
+            result.map_err(|e| Error::new(&format!("{}/{}: Error occurred: {}", entry.day, part, e)))?;
+            samples.push(start.elapsed());
+            if run == 0 {
+                usage = memory;
+            }
+        }
+        measurements.push(Measurement { day: entry.day, phase: Phase::Part(*part), stats: Stats::from_samples(&mut samples), memory: usage });
+    }
+
+    Ok(measurements)
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Relative change of the median compared to a previous run, e.g. "+12.5%".
+fn format_change(current: Duration, previous: Duration) -> String {
+    if previous.as_nanos() == 0 {
+        return "-".to_string();
+    }
+
+    let change = (current.as_nanos() as f64 / previous.as_nanos() as f64 - 1.0) * 100.0;
+    format!("{:+.1}%", change)
+}
+
+/// Prints one row per day and phase and the total of the medians of all
+/// days, optionally compared to the measurements of a previous run. What the
+/// phases allocated is shown if it was measured.
+pub fn print_table(measurements: &[Measurement], previous: Option<&[Measurement]>) {
+    let find_previous = |m: &Measurement| previous
+        .and_then(|p| p.iter().find(|o| o.day == m.day && o.phase == m.phase));
+    let with_memory = measurements.iter().any(|m| m.memory.is_some());
+
+    print!("{:>3}  {:<6} {:>10} {:>10} {:>10}", "day", "phase", "min", "median", "p95");
+    if previous.is_some() {
+        print!(" {:>10}", "change");
+    }
+    if with_memory {
+        print!(" {:>10} {:>10} {:>10}", "allocated", "allocs", "peak");
+    }
+    println!();
+
+    for m in measurements {
+        print!("{:>3}  {:<6} {:>10} {:>10} {:>10}", m.day, m.phase.to_string(),
+            format_duration(m.stats.min), format_duration(m.stats.median), format_duration(m.stats.p95));
+        if previous.is_some() {
+            let change = match find_previous(m) {
+                Some(o) => format_change(m.stats.median, o.stats.median),
+                None => "new".to_string(),
+            };
+            print!(" {:>10}", change);
+        }
+        if let Some(usage) = &m.memory {
+            print!(" {:>10} {:>10} {:>10}", memory::format_bytes(usage.allocated), usage.allocations, memory::format_bytes(usage.peak));
+        }
+        println!();
+    }
+
+    let total: Duration = measurements.iter().map(|m| m.stats.median).sum();
+    print!("{:>3}  {:<6} {:>10} {:>10} {:>10}", "all", "total", "", format_duration(total), "");
+    if let Some(previous) = previous {
+        // only phases measured in both runs are compared
+        let (current_total, previous_total) = measurements.iter()
+            .filter_map(|m| previous.iter().find(|o| o.day == m.day && o.phase == m.phase).map(|o| (m, o)))
+            .fold((Duration::default(), Duration::default()), |(c, p), (m, o)| (c + m.stats.median, p + o.stats.median));
+        print!(" {:>10}", format_change(current_total, previous_total));
+    }
+    println!();
+}
+
+/// Serializes the measurements as one line per day and phase with the
+/// durations in nanoseconds.
+pub fn to_string(measurements: &[Measurement]) -> String {
+    let mut out = String::from("# day phase min median p95\n");
+
+    for m in measurements {
+        out.push_str(&format!("{} {} {} {} {}\n", m.day, m.phase,
+            m.stats.min.as_nanos(), m.stats.median.as_nanos(), m.stats.p95.as_nanos()));
+    }
+
+    out
+}
+
+pub fn from_str(s: &str) -> Result<Vec<Measurement>, Error> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let invalid = || Error::new(&format!("Invalid benchmark line: '{}'", l));
+            let fields = l.split_whitespace().collect::<Vec<_>>();
+            if fields.len() != 5 {
+                return Err(invalid());
+            }
+
+            let day = fields[0].parse::<u8>().map_err(|_| invalid())?;
+            let phase = Phase::from_name(fields[1]).ok_or_else(invalid)?;
+            let nanos = fields[2..].iter()
+                .map(|f| f.parse::<u64>().map(Duration::from_nanos).map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Measurement { day, phase, stats: Stats { min: nanos[0], median: nanos[1], p95: nanos[2] }, memory: None })
+        })
+        .collect()
+}
+
+pub fn save(path: &str, measurements: &[Measurement]) -> Result<(), Error> {
+    fs::write(path, to_string(measurements))
+        .map_err(|e| Error::new(&format!("Unable to save benchmark to '{}': {}", path, e)))
+}
+
+pub fn load(path: &str) -> Result<Vec<Measurement>, Error> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::new(&format!("Unable to read benchmark from '{}': {}", path, e)))?;
+    from_str(&contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn stats_are_taken_from_sorted_samples() {
+        let mut samples = (1..=20).rev().map(Duration::from_millis).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(10), stats.median);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+    }
+
+    #[test]
+    pub fn saved_measurements_can_be_read_back() {
+        let measurements = vec![
+            Measurement { day: 15, phase: Phase::Parse, stats: Stats { min: Duration::from_nanos(5), median: Duration::from_nanos(6), p95: Duration::from_nanos(7) }, memory: None },
+            Measurement { day: 15, phase: Phase::Part(2), stats: Stats { min: Duration::from_secs(1), median: Duration::from_secs(2), p95: Duration::from_secs(3) }, memory: None },
+        ];
+
+        assert_eq!(measurements, from_str(&to_string(&measurements)).unwrap());
+    }
+
+    #[test]
+    pub fn durations_are_formatted_with_a_readable_unit() {
+        assert_eq!("512ns", format_duration(Duration::from_nanos(512)));
+        assert_eq!("1.5µs", format_duration(Duration::from_nanos(1_500)));
+        assert_eq!("2.25ms", format_duration(Duration::from_micros(2_250)));
+        assert_eq!("1.00s", format_duration(Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use super::utils::Error;
+
+/// How many calls to `checkpoint` pass at most before the clock is read
+/// again, so loops with millions of iterations can check on every iteration.
+/// Until then the clock is read on the 1st, 2nd, 4th, ... call, so slow loops
+/// with few iterations notice the timeout as well.
+const CLOCK_INTERVAL: u32 = 1024;
+
+/// Tells a solver when to give up, either because it ran out of time or
+/// because it was cancelled by someone holding a clone of the token.
+#[derive(Clone, Debug)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    timeout: Option<(Instant, Duration)>,
+}
+
+impl Token {
+    /// A token that never times out, but can still be cancelled.
+    pub fn new() -> Self {
+        Token { cancelled: Arc::new(AtomicBool::new(false)), timeout: None }
+    }
+
+    /// A token that times out `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Token { timeout: Some((Instant::now() + timeout, timeout)), ..Token::new() }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        match self.timeout {
+            Some((deadline, timeout)) if Instant::now() >= deadline => Err(Error::Timeout(timeout)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for Token {
+    fn default() -> Self {
+        Token::new()
+    }
+}
+
+/// Runs `f` with a new token that times out after `timeout`, if any.
+pub fn with_timeout<R, F: FnOnce() -> R>(timeout: Option<Duration>, f: F) -> R {
+    let token = match timeout {
+        Some(timeout) => Token::with_timeout(timeout),
+        None => Token::new(),
+    };
+    scope(&token, f)
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+    static CALLS: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Puts the token of the enclosing scope back when a scope ends, also when
+/// it ends with a panic that is caught further up, e.g. by the pool.
+struct Restore(Option<Token>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Runs `f` with `token` as the token `checkpoint` checks on this thread.
+pub fn scope<R, F: FnOnce() -> R>(token: &Token, f: F) -> R {
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some(token.clone()))));
+    CALLS.with(|calls| calls.set(0));
+
+    f()
+}
+
+/// Gives up with an error if the token of the current `scope` was cancelled
+/// or timed out, outside of a scope this always succeeds.
+///
+/// Meant to be called from the loops of long running solvers, e.g.
+/// `cancel::checkpoint()?;` once per iteration.
+pub fn checkpoint() -> Result<(), Error> {
+    let calls = CALLS.with(|calls| {
+        let n = calls.get().wrapping_add(1);
+        calls.set(n);
+        n
+    });
+
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(token) if token.is_cancelled() => Err(Error::Cancelled),
+        Some(token) if calls.is_power_of_two() || calls.is_multiple_of(CLOCK_INTERVAL) => token.check(),
+        _ => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic;
+    use std::thread;
+
+    #[test]
+    pub fn checkpoints_fail_once_the_time_is_up() {
+        let token = Token::with_timeout(Duration::from_millis(20));
+
+        let result: Result<(), Error> = scope(&token, || {
+            loop {
+                checkpoint()?;
+                thread::sleep(Duration::from_micros(10));
+            }
+        });
+
+        assert_eq!("Timed out after 0.02s", result.unwrap_err().to_string());
+        assert!(checkpoint().is_ok());
+    }
+
+    #[test]
+    pub fn tokens_are_removed_when_a_scope_panics() {
+        let token = Token::new();
+        token.cancel();
+
+        let result = panic::catch_unwind(|| scope(&token, || panic!("solver failed")));
+
+        assert!(result.is_err());
+        assert!(checkpoint().is_ok());
+    }
+
+    #[test]
+    pub fn cancelled_tokens_stop_other_threads() {
+        let token = Token::new();
+        let worker = token.clone();
+
+        let handle = thread::spawn(move || scope(&worker, || {
+            loop {
+                checkpoint()?;
+            }
+        }));
+        token.cancel();
+
+        let result: Result<(), Error> = handle.join().unwrap();
+        assert!(matches!(result, Err(Error::Cancelled)));
+    }
+}
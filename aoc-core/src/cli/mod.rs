@@ -0,0 +1,641 @@
+use std::sync::Arc;
+use std::time::Duration;
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use super::frames::DEFAULT_DELAY;
+use super::progress::{self, Progress};
+use super::report::Format;
+use super::utils::{Error, InputSource};
+use super::watch::DEFAULT_INTERVAL;
+
+/// The first year of Advent of Code.
+const FIRST_YEAR: u16 = 2015;
+const FIRST_DAY: u8 = 1;
+const LAST_DAY: u8 = 25;
+
+/// The year, days and parts picked on the command line and where to read
+/// their input from.
+#[derive(Debug, PartialEq)]
+pub struct Selection {
+    pub year: u16,
+    pub days: Vec<u8>,
+    pub parts: Vec<u8>,
+    pub input: InputSource,
+}
+
+pub fn app<'a, 'b>() -> App<'a, 'b> {
+    let app = App::new("aoc")
+        .about("Solutions for Advent of Code")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(selection_args(SubCommand::with_name("run")
+            .about("Runs the solvers of the selected days"))
+            .arg(Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .takes_value(true)
+                .possible_values(&["text", "json", "csv"])
+                .default_value("text")
+                .help("Prints the answers as text, as JSON lines or as CSV"))
+            .arg(Arg::with_name("jobs")
+                .long("jobs")
+                .short("j")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("1")
+                .help("Solves the parts on N threads, the answers are printed in order of day and part nonetheless"))
+            .arg(timeout_arg())
+            .arg(progress_arg()))
+        .subcommand(selection_args(SubCommand::with_name("bench")
+            .about("Measures how long parsing and solving the selected days takes"))
+            .arg(Arg::with_name("runs")
+                .long("runs")
+                .short("n")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("10")
+                .help("How often each phase is run"))
+            .arg(Arg::with_name("save")
+                .long("save")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Saves the results to FILE"))
+            .arg(Arg::with_name("compare")
+                .long("compare")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Compares the results to a previous run saved in FILE")))
+        .subcommand(selection_args(SubCommand::with_name("verify")
+            .about("Checks the answers of the selected days against the answers file"))
+            .arg(Arg::with_name("answers")
+                .long("answers")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Reads the known answers from FILE instead of answers.txt of the year's crate"))
+            .arg(Arg::with_name("record")
+                .long("record")
+                .help("Records the answers of parts that don't have one yet"))
+            .arg(timeout_arg())
+            .arg(progress_arg()))
+        .subcommand(SubCommand::with_name("watch")
+            .about("Solves the inputs and examples of a day again whenever one of them changes")
+            .arg(year_arg())
+            .arg(Arg::with_name("day")
+                .long("day")
+                .short("d")
+                .takes_value(true)
+                .value_name("DAY")
+                .required(true)
+                .help("The day to watch"))
+            .arg(Arg::with_name("part")
+                .long("part")
+                .short("p")
+                .takes_value(true)
+                .possible_values(&["1", "2"])
+                .help("Only solves the given part, solves both parts if omitted"))
+            .arg(Arg::with_name("interval")
+                .long("interval")
+                .takes_value(true)
+                .value_name("MS")
+                .help("Looks for changes every MS milliseconds, 500 if omitted"))
+            .arg(timeout_arg()))
+        .subcommand(SubCommand::with_name("submit")
+            .about("Solves a part with the cached input and submits the answer, unless it is known to be wrong")
+            .arg(year_arg())
+            .arg(Arg::with_name("day")
+                .long("day")
+                .short("d")
+                .takes_value(true)
+                .value_name("DAY")
+                .required(true)
+                .help("The day to submit"))
+            .arg(Arg::with_name("part")
+                .long("part")
+                .short("p")
+                .takes_value(true)
+                .possible_values(&["1", "2"])
+                .required(true)
+                .help("The part to submit"))
+            .arg(Arg::with_name("answer")
+                .long("answer")
+                .takes_value(true)
+                .value_name("ANSWER")
+                .help("Submits ANSWER instead of solving the part"))
+            .arg(Arg::with_name("input")
+                .long("input")
+                .short("i")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Solves the part with the input in FILE or stdin if FILE is '-' instead of the cached input")))
+        .subcommand(SubCommand::with_name("frames")
+            .about("Solves a part of a simulation day and shows or saves the frames it draws on the way")
+            .arg(year_arg())
+            .arg(Arg::with_name("day")
+                .long("day")
+                .short("d")
+                .takes_value(true)
+                .value_name("DAY")
+                .required(true)
+                .help("The day to draw"))
+            .arg(Arg::with_name("part")
+                .long("part")
+                .short("p")
+                .takes_value(true)
+                .possible_values(&["1", "2"])
+                .required(true)
+                .help("The part to draw"))
+            .arg(Arg::with_name("input")
+                .long("input")
+                .short("i")
+                .takes_value(true)
+                .value_name("FILE")
+                .help("Solves the part with the input in FILE or stdin if FILE is '-'"))
+            .arg(Arg::with_name("to")
+                .long("to")
+                .takes_value(true)
+                .value_name("SINK")
+                .default_value("ansi")
+                .help("Animates the frames on the terminal with 'ansi', writes them as text to stdout with 'text' or to FILE with 'text:FILE', or saves them as PPM images in DIR with 'ppm:DIR'"))
+            .arg(Arg::with_name("delay")
+                .long("delay")
+                .takes_value(true)
+                .value_name("MS")
+                .help("Shows each frame of the animation for MS milliseconds, 100 if omitted")))
+        .subcommand(SubCommand::with_name("new")
+            .about("Creates a new day from the template and registers it")
+            .arg(year_arg())
+            .arg(Arg::with_name("day")
+                .long("day")
+                .short("d")
+                .takes_value(true)
+                .value_name("DAY")
+                .required(true)
+                .help("The day to create"))
+            .arg(Arg::with_name("src")
+                .long("src")
+                .takes_value(true)
+                .value_name("DIR")
+                .help("Creates the day in DIR instead of the src directory of the year's crate")));
+
+    #[cfg(feature = "serve")]
+    let app = app.subcommand(SubCommand::with_name("serve")
+        .about("Answers POST /YEAR/day/DAY/part/PART with the input as body with the answer and timings as JSON")
+        .arg(Arg::with_name("addr")
+            .long("addr")
+            .takes_value(true)
+            .value_name("ADDR")
+            .default_value(super::serve::DEFAULT_ADDR)
+            .help("Listens on ADDR"))
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .short("j")
+            .takes_value(true)
+            .value_name("N")
+            .default_value("4")
+            .help("Answers N requests at the same time"))
+        .arg(timeout_arg()));
+
+    app
+}
+
+fn year_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("year")
+        .long("year")
+        .short("y")
+        .takes_value(true)
+        .value_name("YEAR")
+        .help("The year of the days, the latest registered year if omitted")
+}
+
+fn progress_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("progress")
+        .long("progress")
+        .takes_value(true)
+        .possible_values(&["auto", "terminal", "silent", "log"])
+        .default_value("auto")
+        .help("Shows the progress of long running solvers as bars, not at all or as lines for logs, auto shows bars on terminals only")
+}
+
+fn timeout_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("timeout")
+        .long("timeout")
+        .short("t")
+        .takes_value(true)
+        .value_name("SECS")
+        .help("Gives up on parsing an input or solving a part after SECS seconds")
+}
+
+/// Adds the arguments that pick days, parts and the input to a subcommand.
+fn selection_args<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    cmd.arg(year_arg())
+        .arg(Arg::with_name("day")
+            .long("day")
+            .short("d")
+            .takes_value(true)
+            .value_name("DAYS")
+            .help("A single day (7) or an inclusive range of days (10..15)"))
+        .arg(Arg::with_name("all")
+            .long("all")
+            .short("a")
+            .help("Runs all days of the year"))
+        .group(ArgGroup::with_name("days")
+            .args(&["day", "all"])
+            .required(true))
+        .arg(Arg::with_name("part")
+            .long("part")
+            .short("p")
+            .takes_value(true)
+            .possible_values(&["1", "2"])
+            .help("Only runs the given part, runs both parts if omitted"))
+        .arg(Arg::with_name("input")
+            .long("input")
+            .short("i")
+            .takes_value(true)
+            .value_name("FILE")
+            .help("Reads the input from FILE or from stdin if FILE is '-' instead of using the bundled input"))
+        .arg(Arg::with_name("cached")
+            .long("cached")
+            .conflicts_with("input")
+            .help("Uses the inputs in the cache instead of the bundled ones, missing inputs are downloaded with the session in AOC_SESSION"))
+}
+
+fn parse_day(s: &str) -> Result<u8, Error> {
+    let day = s.trim().parse::<u8>()
+        .map_err(|_| Error::new(&format!("Not a day: '{}'", s)))?;
+
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        return Err(Error::new(&format!("Day {} is not between {} and {}", day, FIRST_DAY, LAST_DAY)));
+    }
+
+    Ok(day)
+}
+
+/// Parses a single day ("7") or an inclusive range of days ("10..15" or
+/// "10..=15").
+pub fn parse_days(s: &str) -> Result<Vec<u8>, Error> {
+    if let Some(separator) = s.find("..") {
+        let from = parse_day(&s[..separator])?;
+        let to = parse_day(s[separator + 2..].trim_start_matches('='))?;
+
+        if from > to {
+            return Err(Error::new(&format!("Empty range of days: '{}'", s)));
+        }
+
+        Ok((from..=to).collect())
+    } else {
+        Ok(vec![parse_day(s)?])
+    }
+}
+
+/// The year given on the command line or `default` if none is given.
+pub fn year(matches: &ArgMatches, default: u16) -> Result<u16, Error> {
+    match matches.value_of("year") {
+        Some(year) => match year.trim().parse::<u16>() {
+            Ok(year) if year >= FIRST_YEAR => Ok(year),
+            _ => Err(Error::new(&format!("Not a year of Advent of Code: '{}'", year))),
+        },
+        None => Ok(default),
+    }
+}
+
+/// Picks the days and parts, from `default_year` if no year is given.
+pub fn selection(matches: &ArgMatches, default_year: u16) -> Result<Selection, Error> {
+    let year = year(matches, default_year)?;
+    let days = if matches.is_present("all") {
+        (FIRST_DAY..=LAST_DAY).collect()
+    } else {
+        let days = matches.value_of("day").ok_or(Error::new("No day given"))?;
+        parse_days(days)?
+    };
+
+    let parts = match matches.value_of("part") {
+        Some(part) => vec![part.parse::<u8>().map_err(|_| Error::new(&format!("Not a part: '{}'", part)))?],
+        None => vec![1, 2],
+    };
+
+    let input = if matches.is_present("cached") {
+        InputSource::Cache
+    } else {
+        InputSource::from_arg(matches.value_of("input"))
+    };
+    if matches!(input, InputSource::Stdin | InputSource::File(_)) && days.len() != 1 {
+        return Err(Error::new("A custom input can only be used with a single day"));
+    }
+
+    Ok(Selection { year, days, parts, input })
+}
+
+pub fn format(matches: &ArgMatches) -> Result<Format, Error> {
+    let name = matches.value_of("format").unwrap_or("text");
+    Format::from_name(name).ok_or(Error::new(&format!("Unknown format: '{}'", name)))
+}
+
+pub fn progress(matches: &ArgMatches) -> Result<Arc<dyn Progress>, Error> {
+    let name = matches.value_of("progress").unwrap_or("auto");
+    progress::from_name(name)
+        .ok_or(Error::new(&format!("Unknown progress reporter '{}'", name)))
+}
+
+/// How long parsing the input and solving each part may take.
+pub fn timeout(matches: &ArgMatches) -> Result<Option<Duration>, Error> {
+    match matches.value_of("timeout") {
+        Some(timeout) => match timeout.parse::<f64>().map(Duration::try_from_secs_f64) {
+            Ok(Ok(duration)) if !duration.is_zero() => Ok(Some(duration)),
+            _ => Err(Error::new(&format!("Not a number of seconds: '{}'", timeout))),
+        },
+        None => Ok(None),
+    }
+}
+
+pub fn jobs(matches: &ArgMatches) -> Result<usize, Error> {
+    let jobs = matches.value_of("jobs").unwrap_or("1");
+    match jobs.parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(Error::new(&format!("Not a number of jobs: '{}'", jobs))),
+    }
+}
+
+/// How the selected days are benchmarked.
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub save: Option<String>,
+    pub compare: Option<String>,
+}
+
+pub fn bench_options(matches: &ArgMatches) -> Result<BenchOptions, Error> {
+    let runs = matches.value_of("runs").unwrap_or("10");
+    let runs = runs.parse::<usize>()
+        .map_err(|_| Error::new(&format!("Not a number of runs: '{}'", runs)))?;
+
+    if runs == 0 {
+        return Err(Error::new("At least one run is needed"));
+    }
+
+    let save = matches.value_of("save").map(|s| s.to_string());
+    let compare = matches.value_of("compare").map(|s| s.to_string());
+
+    Ok(BenchOptions { runs, save, compare })
+}
+
+/// Where the known answers are kept, if not in the year's crate, and whether
+/// missing ones are recorded.
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub answers: Option<String>,
+    pub record: bool,
+}
+
+pub fn verify_options(matches: &ArgMatches) -> VerifyOptions {
+    VerifyOptions {
+        answers: matches.value_of("answers").map(|s| s.to_string()),
+        record: matches.is_present("record"),
+    }
+}
+
+/// The day to watch and how often to look for changes.
+#[derive(Debug, PartialEq)]
+pub struct WatchOptions {
+    pub year: u16,
+    pub day: u8,
+    pub parts: Vec<u8>,
+    pub interval: Duration,
+}
+
+pub fn watch_options(matches: &ArgMatches, default_year: u16) -> Result<WatchOptions, Error> {
+    let year = year(matches, default_year)?;
+    let day = parse_day(matches.value_of("day").ok_or(Error::new("No day given"))?)?;
+    let parts = match matches.value_of("part") {
+        Some(part) => vec![part.parse::<u8>().map_err(|_| Error::new(&format!("Not a part: '{}'", part)))?],
+        None => vec![1, 2],
+    };
+    let interval = match matches.value_of("interval") {
+        Some(interval) => match interval.parse::<u64>() {
+            Ok(ms) if ms > 0 => Duration::from_millis(ms),
+            _ => return Err(Error::new(&format!("Not a number of milliseconds: '{}'", interval))),
+        },
+        None => DEFAULT_INTERVAL,
+    };
+
+    Ok(WatchOptions { year, day, parts, interval })
+}
+
+/// What to submit: the year, day and part, where to read the input from and
+/// the answer if it is given on the command line.
+#[derive(Debug, PartialEq)]
+pub struct SubmitOptions {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: InputSource,
+    pub answer: Option<String>,
+}
+
+pub fn submit_options(matches: &ArgMatches, default_year: u16) -> Result<SubmitOptions, Error> {
+    let year = year(matches, default_year)?;
+    let day = parse_day(matches.value_of("day").ok_or(Error::new("No day given"))?)?;
+    let part = matches.value_of("part").ok_or(Error::new("No part given"))?;
+    let part = part.parse::<u8>().map_err(|_| Error::new(&format!("Not a part: '{}'", part)))?;
+
+    // submitting is only ever meant for the user's own input
+    let input = match matches.value_of("input") {
+        None => InputSource::Cache,
+        input => InputSource::from_arg(input),
+    };
+    let answer = matches.value_of("answer").map(|s| s.to_string());
+
+    Ok(SubmitOptions { year, day, part, input, answer })
+}
+
+/// What to draw: the year, day and part, where to read the input from, the
+/// sink to hand the frames to and how long the animation shows each one.
+#[derive(Debug, PartialEq)]
+pub struct FramesOptions {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: InputSource,
+    pub sink: String,
+    pub delay: Duration,
+}
+
+pub fn frames_options(matches: &ArgMatches, default_year: u16) -> Result<FramesOptions, Error> {
+    let year = year(matches, default_year)?;
+    let day = parse_day(matches.value_of("day").ok_or(Error::new("No day given"))?)?;
+    let part = matches.value_of("part").ok_or(Error::new("No part given"))?;
+    let part = part.parse::<u8>().map_err(|_| Error::new(&format!("Not a part: '{}'", part)))?;
+    let input = InputSource::from_arg(matches.value_of("input"));
+    let sink = matches.value_of("to").unwrap_or("ansi").to_string();
+    let delay = match matches.value_of("delay") {
+        Some(delay) => delay.parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| Error::new(&format!("Not a number of milliseconds: '{}'", delay)))?,
+        None => DEFAULT_DELAY,
+    };
+
+    Ok(FramesOptions { year, day, part, input, sink, delay })
+}
+
+/// The year and day to create and the source directory to create it in, if
+/// not the one of the year's crate.
+pub fn new_options(matches: &ArgMatches, default_year: u16) -> Result<(u16, u8, Option<String>), Error> {
+    let year = year(matches, default_year)?;
+    let day = parse_day(matches.value_of("day").ok_or(Error::new("No day given"))?)?;
+    let src = matches.value_of("src").map(|s| s.to_string());
+
+    Ok((year, day, src))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn single_day_can_be_parsed() {
+        assert_eq!(vec![7], parse_days("7").unwrap());
+    }
+
+    #[test]
+    pub fn range_of_days_is_inclusive() {
+        assert_eq!(vec![10, 11, 12, 13, 14, 15], parse_days("10..15").unwrap());
+        assert_eq!(vec![10, 11, 12, 13, 14, 15], parse_days("10..=15").unwrap());
+    }
+
+    #[test]
+    pub fn days_outside_of_advent_are_rejected() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("24..26").is_err());
+        assert!(parse_days("15..10").is_err());
+    }
+
+    #[test]
+    pub fn all_days_and_parts_are_selected() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all"]);
+        let selection = selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap();
+
+        assert_eq!(25, selection.days.len());
+        assert_eq!(vec![1, 2], selection.parts);
+    }
+
+    #[test]
+    pub fn year_defaults_to_the_given_one() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--day", "7"]);
+        assert_eq!(2020, selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap().year);
+
+        let matches = app().get_matches_from(vec!["aoc", "run", "--year", "2015", "--day", "7"]);
+        assert_eq!(2015, selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap().year);
+
+        let matches = app().get_matches_from(vec!["aoc", "run", "-y", "1999", "--all"]);
+        assert!(selection(matches.subcommand_matches("run").unwrap(), 2020).is_err());
+    }
+
+    #[test]
+    pub fn day_and_part_are_selected() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--day", "7", "--part", "2"]);
+        let selection = selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap();
+
+        assert_eq!(Selection { year: 2020, days: vec![7], parts: vec![2], input: InputSource::Bundled }, selection);
+    }
+
+    #[test]
+    pub fn input_can_be_read_from_stdin() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--day", "7", "--input", "-"]);
+        let selection = selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap();
+
+        assert_eq!(InputSource::Stdin, selection.input);
+    }
+
+    #[test]
+    pub fn custom_input_is_rejected_for_multiple_days() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--day", "7..8", "--input", "input.txt"]);
+
+        assert!(selection(matches.subcommand_matches("run").unwrap(), 2020).is_err());
+    }
+
+    #[test]
+    pub fn cached_inputs_can_be_used_for_all_days() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all", "--cached"]);
+        assert_eq!(InputSource::Cache, selection(matches.subcommand_matches("run").unwrap(), 2020).unwrap().input);
+
+        let matches = app().get_matches_from_safe(vec!["aoc", "run", "--day", "1", "--cached", "--input", "-"]);
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    pub fn output_format_defaults_to_text() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all"]);
+        assert_eq!(Format::Text, format(matches.subcommand_matches("run").unwrap()).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all", "--format", "csv"]);
+        assert_eq!(Format::Csv, format(matches.subcommand_matches("run").unwrap()).unwrap());
+    }
+
+    #[test]
+    pub fn bench_takes_a_selection_and_options() {
+        let matches = app().get_matches_from(vec!["aoc", "bench", "--day", "15", "-n", "3", "--save", "bench.txt"]);
+        let matches = matches.subcommand_matches("bench").unwrap();
+
+        assert_eq!(vec![15], selection(matches, 2020).unwrap().days);
+        assert_eq!(BenchOptions { runs: 3, save: Some("bench.txt".to_string()), compare: None }, bench_options(matches).unwrap());
+    }
+
+    #[test]
+    pub fn watch_takes_a_single_day() {
+        let matches = app().get_matches_from(vec!["aoc", "watch", "--day", "7", "-p", "1", "--interval", "200"]);
+        assert_eq!(WatchOptions { year: 2020, day: 7, parts: vec![1], interval: Duration::from_millis(200) },
+            watch_options(matches.subcommand_matches("watch").unwrap(), 2020).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "watch", "--day", "7..8"]);
+        assert!(watch_options(matches.subcommand_matches("watch").unwrap(), 2020).is_err());
+    }
+
+    #[test]
+    pub fn submit_uses_the_cached_input_by_default() {
+        let matches = app().get_matches_from(vec!["aoc", "submit", "--day", "1", "--part", "2"]);
+        assert_eq!(SubmitOptions { year: 2020, day: 1, part: 2, input: InputSource::Cache, answer: None },
+            submit_options(matches.subcommand_matches("submit").unwrap(), 2020).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "submit", "-d", "3", "-p", "1", "--answer", "42"]);
+        assert_eq!(Some("42".to_string()), submit_options(matches.subcommand_matches("submit").unwrap(), 2020).unwrap().answer);
+
+        let matches = app().get_matches_from_safe(vec!["aoc", "submit", "--day", "1"]);
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    pub fn frames_are_animated_by_default() {
+        let matches = app().get_matches_from(vec!["aoc", "frames", "--day", "11", "--part", "1"]);
+        assert_eq!(FramesOptions {
+            year: 2020, day: 11, part: 1, input: InputSource::Bundled, sink: "ansi".to_string(), delay: Duration::from_millis(100),
+        }, frames_options(matches.subcommand_matches("frames").unwrap(), 2020).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "frames", "-d", "17", "-p", "2", "--to", "ppm:frames", "--delay", "0"]);
+        let options = frames_options(matches.subcommand_matches("frames").unwrap(), 2020).unwrap();
+        assert_eq!(("ppm:frames", Duration::from_millis(0)), (options.sink.as_str(), options.delay));
+
+        let matches = app().get_matches_from(vec!["aoc", "frames", "-d", "24", "-p", "1", "--delay", "soon"]);
+        assert!(frames_options(matches.subcommand_matches("frames").unwrap(), 2020).is_err());
+    }
+
+    #[test]
+    pub fn progress_reporter_can_be_picked() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all", "--progress", "log"]);
+        assert!(progress(matches.subcommand_matches("run").unwrap()).is_ok());
+
+        let matches = app().get_matches_from_safe(vec!["aoc", "run", "--all", "--progress", "bars"]);
+        assert!(matches.is_err());
+    }
+
+    #[test]
+    pub fn timeout_is_given_in_seconds() {
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all"]);
+        assert_eq!(None, timeout(matches.subcommand_matches("run").unwrap()).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "verify", "--all", "--timeout", "2.5"]);
+        assert_eq!(Some(Duration::from_millis(2500)), timeout(matches.subcommand_matches("verify").unwrap()).unwrap());
+
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all", "--timeout", "0"]);
+        assert!(timeout(matches.subcommand_matches("run").unwrap()).is_err());
+
+        let matches = app().get_matches_from(vec!["aoc", "run", "--all", "--timeout", "1e30"]);
+        assert!(timeout(matches.subcommand_matches("run").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use super::utils::grid::Grid;
+use super::utils::Error;
+
+/// How long the terminal animation shows each frame if no delay is given.
+pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// How many pixels wide and high a cell is in PPM images.
+const PPM_SCALE: usize = 4;
+
+/// One picture of a simulation, e.g. the seats of day 11 after a round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub title: String,
+    pub cells: Grid<char>,
+}
+
+impl Frame {
+    pub fn new(title: &str, cells: Grid<char>) -> Self {
+        Frame { title: title.to_string(), cells }
+    }
+}
+
+/// The color of a cell in animations and images: `#` is bright, `.` and
+/// spaces are dark, as that's how the puzzles draw what is on and off.
+pub fn color(cell: char) -> [u8; 3] {
+    match cell {
+        '#' => [255, 204, 0],
+        'L' => [64, 160, 64],
+        'O' => [64, 128, 255],
+        '.' => [32, 32, 32],
+        ' ' => [0, 0, 0],
+        _ => [160, 160, 160],
+    }
+}
+
+/// Receives the frames of the simulations, to show or save them.
+pub trait Sink: Send {
+    fn frame(&mut self, frame: &Frame) -> Result<(), Error>;
+}
+
+/// A sink that can be handed to `scope` and shared between threads.
+pub type SharedSink = Arc<Mutex<dyn Sink>>;
+
+fn write_error(e: io::Error) -> Error {
+    Error::new(&format!("Unable to write frame: {}", e))
+}
+
+/// Animates the frames on a terminal by drawing each one over the last.
+pub struct Ansi<W: Write> {
+    out: W,
+    delay: Duration,
+}
+
+impl<W: Write> Ansi<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Ansi { out, delay }
+    }
+}
+
+impl<W: Write + Send> Sink for Ansi<W> {
+    fn frame(&mut self, frame: &Frame) -> Result<(), Error> {
+        // move to the top left and clear the screen
+        let mut text = format!("\x1b[H\x1b[2J{}\n", frame.title);
+        for row in frame.cells.rows() {
+            for cell in row {
+                let [r, g, b] = color(*cell);
+                text.push_str(&format!("\x1b[48;2;{};{};{}m  ", r, g, b));
+            }
+            text.push_str("\x1b[0m\n");
+        }
+
+        self.out.write_all(text.as_bytes()).and_then(|_| self.out.flush()).map_err(write_error)?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+/// Writes the frames one after the other as text, each below its title.
+pub struct Text<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Text<W> {
+    pub fn new(out: W) -> Self {
+        Text { out }
+    }
+}
+
+impl<W: Write + Send> Sink for Text<W> {
+    fn frame(&mut self, frame: &Frame) -> Result<(), Error> {
+        write!(self.out, "{}\n{}\n", frame.title, frame.cells).and_then(|_| self.out.flush()).map_err(write_error)
+    }
+}
+
+/// Saves every frame as numbered PPM image in a directory, e.g. to turn
+/// them into a video. Frames can differ in size, the images do as well.
+pub struct Ppm {
+    dir: PathBuf,
+    count: usize,
+}
+
+impl Ppm {
+    pub fn new(dir: &Path) -> Result<Self, Error> {
+        fs::create_dir_all(dir)
+            .map_err(|e| Error::new(&format!("Unable to create '{}': {}", dir.display(), e)))?;
+
+        Ok(Ppm { dir: dir.to_path_buf(), count: 0 })
+    }
+}
+
+/// The binary PPM image of `cells`, each one a square of `scale` pixels.
+pub fn ppm(cells: &Grid<char>, scale: usize) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", cells.width() * scale, cells.height() * scale).into_bytes();
+    for row in cells.rows() {
+        let line = row.iter()
+            .flat_map(|cell| color(*cell).repeat(scale))
+            .collect::<Vec<_>>();
+        for _ in 0..scale {
+            image.extend(&line);
+        }
+    }
+
+    image
+}
+
+impl Sink for Ppm {
+    fn frame(&mut self, frame: &Frame) -> Result<(), Error> {
+        self.count += 1;
+        let path = self.dir.join(format!("frame-{:05}.ppm", self.count));
+
+        File::create(&path)
+            .and_then(|file| {
+                let mut out = BufWriter::new(file);
+                out.write_all(&ppm(&frame.cells, PPM_SCALE))?;
+                out.flush()
+            })
+            .map_err(|e| Error::new(&format!("Unable to write '{}': {}", path.display(), e)))
+    }
+}
+
+/// Picks a sink by how it's given on the command line: `ansi` animates on
+/// stdout showing each frame for `delay`, `text` writes to stdout and
+/// `text:FILE` to FILE, `ppm:DIR` saves images in DIR.
+pub fn from_spec(spec: &str, delay: Duration) -> Result<SharedSink, Error> {
+    let sink: SharedSink = match spec.split_once(':') {
+        None if spec == "ansi" => Arc::new(Mutex::new(Ansi::new(io::stdout(), delay))),
+        None if spec == "text" => Arc::new(Mutex::new(Text::new(io::stdout()))),
+        Some(("text", path)) => {
+            let file = File::create(path)
+                .map_err(|e| Error::new(&format!("Unable to create '{}': {}", path, e)))?;
+            Arc::new(Mutex::new(Text::new(BufWriter::new(file))))
+        },
+        Some(("ppm", dir)) => Arc::new(Mutex::new(Ppm::new(Path::new(dir))?)),
+        _ => return Err(Error::new(&format!("Unknown frame sink '{}', expected ansi, text, text:FILE or ppm:DIR", spec))),
+    };
+
+    Ok(sink)
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<(SharedSink, String)>> = const { RefCell::new(None) };
+}
+
+/// Puts the sink of the enclosing scope back when a scope ends, also when it
+/// ends with a panic that is caught further up.
+struct Restore(Option<(SharedSink, String)>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Runs `f` with `sink` receiving the frames emitted on this thread, their
+/// titles are prefixed with `label`, e.g. "2020/11/1".
+pub fn scope<R, F: FnOnce() -> R>(sink: &SharedSink, label: &str, f: F) -> R {
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some((Arc::clone(sink), label.to_string())))));
+
+    f()
+}
+
+/// Hands the frame `draw` draws to the sink of the current `scope`. Outside
+/// of one nothing is drawn, so solvers can emit frames at no cost.
+pub fn emit<F: FnOnce() -> Frame>(draw: F) -> Result<(), Error> {
+    let current = CURRENT.with(|current| current.borrow().clone());
+
+    match current {
+        Some((sink, label)) => {
+            let mut frame = draw();
+            frame.title = format!("{} {}", label, frame.title);
+            // a sink that panicked on an earlier frame may be half way through
+            // writing it, so it doesn't get any more
+            sink.lock()
+                .map_err(|_| Error::new("The frame sink failed on an earlier frame"))?
+                .frame(&frame)
+        },
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+
+    /// Keeps the frames it receives.
+    #[derive(Default)]
+    struct Frames(Vec<Frame>);
+
+    impl Sink for Frames {
+        fn frame(&mut self, frame: &Frame) -> Result<(), Error> {
+            self.0.push(frame.clone());
+            Ok(())
+        }
+    }
+
+    fn cells(rows: &[&str]) -> Grid<char> {
+        Grid::from_rows(rows.iter().map(|row| row.chars().collect()).collect()).unwrap()
+    }
+
+    #[test]
+    pub fn frames_are_emitted_within_a_scope_only() {
+        let frames = Arc::new(Mutex::new(Frames::default()));
+        let sink: SharedSink = frames.clone();
+
+        emit(|| panic!("There is no sink to draw for")).unwrap();
+        scope(&sink, "2020/11/1", || emit(|| Frame::new("round 1", cells(&["#."])))).unwrap();
+
+        assert_eq!(vec![Frame::new("2020/11/1 round 1", cells(&["#."]))], frames.lock().unwrap().0);
+    }
+
+    /// Panics on every frame.
+    struct Broken;
+
+    impl Sink for Broken {
+        fn frame(&mut self, _frame: &Frame) -> Result<(), Error> {
+            panic!("Unable to draw")
+        }
+    }
+
+    #[test]
+    pub fn panicked_sinks_fail_later_frames() {
+        let sink: SharedSink = Arc::new(Mutex::new(Broken));
+        let draw = || Frame::new("round 1", cells(&["#."]));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| scope(&sink, "2020/11/1", || emit(draw))));
+        assert!(result.is_err());
+        // the scope that panicked is gone
+        assert!(emit(|| panic!("There is no sink to draw for")).is_ok());
+
+        let result = scope(&sink, "2020/11/1", || emit(draw));
+        assert_eq!("The frame sink failed on an earlier frame", result.unwrap_err().to_string());
+    }
+
+    #[test]
+    pub fn frames_are_written_as_text_and_images() {
+        let frame = Frame::new("round 2", cells(&["#.", "L#"]));
+
+        let mut text = Text::new(vec![]);
+        text.frame(&frame).unwrap();
+        assert_eq!("round 2\n#.\nL#\n\n", String::from_utf8(text.out).unwrap());
+
+        let image = ppm(&frame.cells, 2);
+        assert!(image.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(b"P6\n4 4\n255\n".len() + 4 * 4 * 3, image.len());
+        assert_eq!(&[255, 204, 0, 255, 204, 0, 32, 32, 32], &image[11..20]);
+    }
+}
@@ -0,0 +1,229 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use super::utils::Error;
+
+/// Where inputs are downloaded from if no other base URL is configured.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Tells the server who is asking.
+pub(crate) const USER_AGENT: &str = concat!("aoc-core/", env!("CARGO_PKG_VERSION"));
+
+/// How long to wait between two requests at least, to go easy on the
+/// server.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// When the last request of this process to the server started, shared by
+/// downloads and submissions.
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Where inputs are cached and how missing ones are downloaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub base_url: String,
+    /// The value of the `session` cookie of a logged in user
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// `$XDG_<KIND>_HOME/aoc`, or `$HOME/<fallback>/aoc`.
+fn user_dir(kind: &str, fallback: &str) -> Option<PathBuf> {
+    env_var(&format!("XDG_{}_HOME", kind)).map(PathBuf::from)
+        .or_else(|| env_var("HOME").map(|home| Path::new(&home).join(fallback)))
+        .map(|dir| dir.join("aoc"))
+}
+
+impl Config {
+    /// Reads the configuration from the environment:
+    ///
+    /// - the session from `AOC_SESSION` or the file `aoc/session` in
+    ///   the user's config directory, e.g. `~/.config/aoc/session`
+    /// - the cache directory from `AOC_CACHE`, by default
+    ///   `aoc` in the user's cache directory, e.g. `~/.cache/aoc`
+    /// - the base URL from `AOC_BASE_URL`
+    pub fn from_env() -> Result<Config, Error> {
+        let session = match env_var("AOC_SESSION") {
+            Some(session) => Some(session),
+            None => user_dir("CONFIG", ".config")
+                .map(|dir| dir.join("session"))
+                .filter(|path| path.exists())
+                .map(|path| fs::read_to_string(&path)
+                    .map_err(|e| Error::new(&format!("Unable to read the session from '{}': {}", path.display(), e))))
+                .transpose()?,
+        };
+
+        let cache_dir = env_var("AOC_CACHE").map(PathBuf::from)
+            .or_else(|| user_dir("CACHE", ".cache"))
+            .ok_or(Error::new("Unable to find a cache directory for inputs, set AOC_CACHE"))?;
+
+        Ok(Config {
+            base_url: env_var("AOC_BASE_URL").unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            session: session.map(|s| s.trim().to_string()),
+            cache_dir,
+            min_interval: DEFAULT_MIN_INTERVAL,
+        })
+    }
+}
+
+/// Blocks until `min_interval` has passed since the last request to the
+/// server.
+pub(crate) fn wait_for_turn(min_interval: Duration) {
+    let mut last = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(last) = *last {
+        let elapsed = last.elapsed();
+        if elapsed < min_interval {
+            thread::sleep(min_interval - elapsed);
+        }
+    }
+    *last = Some(Instant::now());
+}
+
+/// The inputs of the user, read from the cache or downloaded if they aren't
+/// cached yet.
+pub struct Inputs {
+    config: Config,
+}
+
+impl Inputs {
+    pub fn new(config: Config) -> Self {
+        Inputs { config }
+    }
+
+    /// Where the input of a day is cached.
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.config.cache_dir.join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return fs::read_to_string(&path)
+                .map_err(|e| Error::new(&format!("Unable to read cached input '{}': {}", path.display(), e)));
+        }
+
+        let input = self.download(year, day)?;
+        self.store(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Writes to a temporary file first, so an interrupted write never leaves
+    /// a truncated input in the cache.
+    fn store(&self, path: &Path, input: &str) -> Result<(), Error> {
+        let error = |e: std::io::Error| Error::new(&format!("Unable to cache input in '{}': {}", path.display(), e));
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, input).map_err(error)?;
+        fs::rename(&partial, path).map_err(error)
+    }
+
+    fn download(&self, year: u16, day: u8) -> Result<String, Error> {
+        let session = self.config.session.as_ref()
+            .ok_or(Error::new(&format!("Input of day {} isn't cached and there is no session to download it, \
+                set AOC_SESSION or save the session cookie in the config directory", day)))?;
+
+        wait_for_turn(self.config.min_interval);
+
+        let url = format!("{}/{}/day/{}/input", self.config.base_url.trim_end_matches('/'), year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .timeout_connect(10_000)
+            .timeout_read(30_000)
+            .call();
+
+        if let Some(err) = response.synthetic_error() {
+            return Err(Error::new(&format!("Unable to download input of day {} from '{}': {}", day, url, err)));
+        }
+
+        match response.status() {
+            200 => response.into_string()
+                .map_err(|e| Error::new(&format!("Unable to read input of day {} from '{}': {}", day, url, e))),
+            400 | 401 | 403 => Err(Error::new(&format!(
+                "Downloading input of day {} was refused ({}), the session is probably invalid or expired", day, response.status()))),
+            404 => Err(Error::new(&format!("There is no input for day {} of {} (yet)", day, year))),
+            status => Err(Error::new(&format!("Unable to download input of day {} from '{}': {} {}",
+                day, url, status, response.status_text()))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod stub;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::stub;
+
+    fn config(name: &str, base_url: &str) -> Config {
+        let cache_dir = env::temp_dir().join(format!("aoc-inputs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+
+        Config {
+            base_url: base_url.to_string(),
+            session: Some("53cr3t".to_string()),
+            cache_dir,
+            min_interval: Duration::from_millis(0),
+        }
+    }
+
+    #[test]
+    pub fn missing_inputs_are_downloaded_and_cached() {
+        let (url, requests) = stub::serve(vec![(200, "1721\n979\n")]);
+        let inputs = Inputs::new(config("download", &url));
+
+        assert_eq!("1721\n979\n", inputs.get(2020, 1).unwrap());
+        let request = requests.recv().unwrap();
+        assert_eq!("GET /2020/day/1/input HTTP/1.1", request.line);
+        assert_eq!("session=53cr3t", request.cookie);
+
+        // the stub is done, so this has to come from the cache
+        assert_eq!("1721\n979\n", inputs.get(2020, 1).unwrap());
+        assert!(inputs.path(2020, 1).exists());
+
+        fs::remove_dir_all(&inputs.config.cache_dir).unwrap();
+    }
+
+    #[test]
+    pub fn failed_downloads_are_explained() {
+        let (url, _requests) = stub::serve(vec![
+            (400, "Puzzle inputs differ by user."), (404, "Not found"), (500, "Internal Server Error"),
+        ]);
+        let inputs = Inputs::new(config("errors", &url));
+
+        let err = inputs.get(2020, 2).unwrap_err().to_string();
+        assert!(err.contains("session is probably invalid"), "{}", err);
+        let err = inputs.get(2020, 26).unwrap_err().to_string();
+        assert!(err.contains("no input for day 26"), "{}", err);
+        let err = inputs.get(2020, 2).unwrap_err().to_string();
+        assert!(err.contains(": 500 ") && !err.contains("session"), "{}", err);
+        assert!(!inputs.path(2020, 2).exists());
+
+        let inputs = Inputs::new(Config { session: None, ..config("no-session", &url) });
+        assert!(inputs.get(2020, 3).unwrap_err().to_string().contains("no session"));
+    }
+
+    #[test]
+    pub fn downloads_are_rate_limited() {
+        let (url, _requests) = stub::serve(vec![(200, "a"), (200, "b")]);
+        let inputs = Inputs::new(Config { min_interval: Duration::from_millis(300), ..config("rate", &url) });
+
+        let start = Instant::now();
+        inputs.get(2020, 4).unwrap();
+        inputs.get(2020, 5).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        fs::remove_dir_all(&inputs.config.cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+//! A tiny HTTP server standing in for adventofcode.com in tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+/// What the stub received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    /// e.g. "GET /2020/day/1/input HTTP/1.1"
+    pub line: String,
+    pub cookie: String,
+    pub body: String,
+}
+
+/// Answers the next `responses.len()` requests with the given status and
+/// body. Returns the base URL of the stub and the requests it received.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let (mut cookie, mut length) = (String::new(), 0);
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+
+                let (name, value) = header.split_at(header.find(':').unwrap_or(header.len()));
+                let value = value.trim_start_matches(':').trim();
+                match name.to_lowercase().as_str() {
+                    "cookie" => cookie = value.to_string(),
+                    "content-length" => length = value.parse().unwrap(),
+                    _ => {},
+                }
+            }
+
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            let request = Request {
+                line: line.trim().to_string(),
+                cookie,
+                body: String::from_utf8(request_body).unwrap(),
+            };
+            let _ = tx.send(request);
+
+            write!(stream, "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+        }
+    });
+
+    (url, rx)
+}
@@ -0,0 +1,30 @@
+//! What the solutions of every year share: the `Solution` trait and the
+//! `Registry` the years add their days to, parsing helpers and errors, and
+//! running, benchmarking, verifying and watching the registered days.
+
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+extern crate num;
+extern crate clap;
+
+pub mod utils;
+pub mod answer;
+pub mod solution;
+pub mod registry;
+pub mod cli;
+pub mod bench;
+pub mod verify;
+pub mod report;
+pub mod pool;
+pub mod runner;
+pub mod scaffold;
+pub mod cancel;
+pub mod progress;
+pub mod memory;
+pub mod inputs;
+pub mod submit;
+pub mod watch;
+pub mod frames;
+#[cfg(feature = "serve")]
+pub mod serve;
@@ -0,0 +1,142 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Forwards to the system allocator and counts the allocations of every
+/// thread, so `measure` can tell how much memory a solver needs. Installed by
+/// the binary if the `alloc-stats` feature is enabled:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: memory::Counting = memory::Counting;
+/// ```
+pub struct Counting;
+
+/// Set on the first allocation through `Counting`, so `measure` knows whether
+/// its numbers mean anything.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    allocated: u64,
+    allocations: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    // const initialized and without destructor, as allocating while
+    // allocating doesn't end well
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { allocated: 0, allocations: 0, live: 0, peak: 0 })
+    };
+}
+
+fn count(allocated: usize, change: isize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+
+    // the counters are gone while the thread is torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if allocated > 0 {
+            c.allocated += allocated as u64;
+            c.allocations += 1;
+        }
+        c.live += change as i64;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size(), layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size(), layout.size() as isize);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count(0, -(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size, new_size as isize - layout.size() as isize);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// What a piece of code allocated on its thread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    /// Bytes allocated in total, whether they were freed again or not
+    pub allocated: u64,
+    pub allocations: u64,
+    /// The most bytes that were allocated at the same time, on top of what
+    /// was allocated before
+    pub peak: u64,
+}
+
+/// Runs `f` and counts its allocations, `None` if the `Counting` allocator
+/// isn't installed.
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> (R, Option<Usage>) {
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        let before = c;
+        c.peak = c.live;
+        counters.set(c);
+        before
+    });
+
+    let result = f();
+
+    let after = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        let after = c;
+        c.peak = c.peak.max(before.peak);
+        counters.set(c);
+        after
+    });
+
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return (result, None);
+    }
+
+    let usage = Usage {
+        allocated: after.allocated - before.allocated,
+        allocations: after.allocations - before.allocations,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(usage))
+}
+
+/// E.g. "512B", "1.5KiB" or "12.0MiB".
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{}B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1}KiB", bytes as f64 / 1024.0)
+    } else if bytes < 1024 * 1024 * 1024 {
+        format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2}GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn bytes_are_formatted_with_binary_prefixes() {
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.5KiB", format_bytes(1536));
+        assert_eq!("12.0MiB", format_bytes(12 * 1024 * 1024));
+    }
+}
@@ -0,0 +1,103 @@
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// Runs `f` on every item on `workers` threads and hands the results to
+/// `consume` in the order of the items, no matter in which order they are
+/// done.
+///
+/// A panic in `f` doesn't take down the pool, it's passed on as the `Err`
+/// of that item.
+pub fn map_ordered<T, R, F, C>(items: Vec<T>, workers: usize, f: F, mut consume: C)
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+    C: FnMut(Result<R, String>),
+{
+    let queue = Arc::new(Mutex::new(items.into_iter().enumerate().collect::<VecDeque<_>>()));
+    let f = Arc::new(f);
+    let (tx, rx) = mpsc::channel();
+
+    let handles = (0..workers.max(1))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let f = Arc::clone(&f);
+            let tx = tx.clone();
+
+            thread::spawn(move || loop {
+                let job = queue.lock().unwrap().pop_front();
+                let (index, item) = match job {
+                    Some(job) => job,
+                    None => break,
+                };
+
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)))
+                    .map_err(|e| panic_message(e.as_ref()));
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut done = BTreeMap::new();
+    let mut next = 0;
+    for (index, result) in rx {
+        done.insert(index, result);
+        while let Some(result) = done.remove(&next) {
+            consume(result);
+            next += 1;
+        }
+    }
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        format!("Panicked: {}", s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        format!("Panicked: {}", s)
+    } else {
+        "Panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    pub fn results_are_consumed_in_order() {
+        let mut results = Vec::new();
+
+        // later items finish first
+        map_ordered((0..8u64).collect(), 4, |i| {
+            thread::sleep(Duration::from_millis(40 - i * 5));
+            i * i
+        }, |r| results.push(r.unwrap()));
+
+        assert_eq!(vec![0, 1, 4, 9, 16, 25, 36, 49], results);
+    }
+
+    #[test]
+    pub fn panics_are_turned_into_errors() {
+        let mut results = Vec::new();
+
+        map_ordered(vec![1, 0, 2], 2, |i| {
+            if i == 0 {
+                panic!("division by zero");
+            }
+            10 / i
+        }, |r| results.push(r));
+
+        assert_eq!(vec![Ok(10), Err("Panicked: division by zero".to_string()), Ok(5)], results);
+    }
+}
@@ -0,0 +1,261 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+use std::sync::{Arc, Mutex};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// The most steps between two updates of a task with an unknown total.
+const MAX_UNKNOWN_STEP: u64 = 1 << 16;
+
+/// Shows how far long running solvers are. Solvers don't use this directly,
+/// they report through a `Task`.
+pub trait Progress: Send + Sync {
+    /// `task` started, `total` is 0 if it's unknown how many steps it takes.
+    fn start(&self, task: &str, total: u64);
+
+    /// `done` steps of `task` are done.
+    fn update(&self, task: &str, done: u64);
+
+    fn finish(&self, task: &str);
+}
+
+/// Doesn't show anything.
+pub struct Silent;
+
+impl Progress for Silent {
+    fn start(&self, _task: &str, _total: u64) {}
+
+    fn update(&self, _task: &str, _done: u64) {}
+
+    fn finish(&self, _task: &str) {}
+}
+
+/// Shows a progress bar per task on the terminal, the bars of tasks running
+/// on several threads are drawn below each other.
+#[derive(Default)]
+pub struct Terminal {
+    multi: MultiProgress,
+    bars: Mutex<HashMap<String, ProgressBar>>,
+}
+
+impl Progress for Terminal {
+    fn start(&self, task: &str, total: u64) {
+        let bar = if total > 0 {
+            let bar = ProgressBar::new(total);
+            bar.set_style(ProgressStyle::with_template("{msg} {wide_bar} {pos}/{len}").expect("The template is valid"));
+            bar
+        } else {
+            ProgressBar::new_spinner()
+        };
+        bar.set_message(task.to_string());
+
+        let bar = self.multi.add(bar);
+        self.bars.lock().unwrap().insert(task.to_string(), bar);
+    }
+
+    fn update(&self, task: &str, done: u64) {
+        if let Some(bar) = self.bars.lock().unwrap().get(task) {
+            bar.set_position(done);
+        }
+    }
+
+    fn finish(&self, task: &str) {
+        if let Some(bar) = self.bars.lock().unwrap().remove(task) {
+            bar.finish_and_clear();
+            self.multi.remove(&bar);
+        }
+    }
+}
+
+/// Writes a line like `progress task="23/2 Moving cups" done=1000000
+/// total=10000000` to stderr every 10 percent, or whenever the steps double
+/// if the total is unknown. Meant for logs of CI runs.
+#[derive(Default)]
+pub struct Log {
+    reported: Mutex<HashMap<String, (u64, u64)>>,
+}
+
+impl Progress for Log {
+    fn start(&self, task: &str, total: u64) {
+        self.reported.lock().unwrap().insert(task.to_string(), (total, 0));
+        eprintln!("progress task={:?} done=0 total={}", task, total);
+    }
+
+    fn update(&self, task: &str, done: u64) {
+        let mut reported = self.reported.lock().unwrap();
+        if let Some((total, mark)) = reported.get_mut(task) {
+            let now = (done * 10).checked_div(*total)
+                .unwrap_or(64 - done.leading_zeros() as u64);
+            if now > *mark {
+                *mark = now;
+                eprintln!("progress task={:?} done={} total={}", task, done, total);
+            }
+        }
+    }
+
+    fn finish(&self, task: &str) {
+        self.reported.lock().unwrap().remove(task);
+        eprintln!("progress task={:?} finished", task);
+    }
+}
+
+/// Picks a reporter by its name on the command line, `auto` shows progress
+/// bars if stderr is a terminal and nothing otherwise.
+pub fn from_name(name: &str) -> Option<Arc<dyn Progress>> {
+    match name {
+        "auto" if io::stderr().is_terminal() => Some(Arc::new(Terminal::default())),
+        "auto" | "silent" => Some(Arc::new(Silent)),
+        "terminal" => Some(Arc::new(Terminal::default())),
+        "log" => Some(Arc::new(Log::default())),
+        _ => None,
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<(Arc<dyn Progress>, String)>> = const { RefCell::new(None) };
+}
+
+/// Puts the reporter of the enclosing scope back when a scope ends, also
+/// when it ends with a panic that is caught further up.
+struct Restore(Option<(Arc<dyn Progress>, String)>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Runs `f` with `progress` as the reporter of the tasks started on this
+/// thread, their names are prefixed with `label`, e.g. "23/2".
+pub fn scope<R, F: FnOnce() -> R>(progress: &Arc<dyn Progress>, label: &str, f: F) -> R {
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some((Arc::clone(progress), label.to_string())))));
+
+    f()
+}
+
+/// A long running loop of a solver, reported to the reporter of the current
+/// `scope`, or to nowhere outside of one. Finishes when it's dropped.
+pub struct Task {
+    progress: Arc<dyn Progress>,
+    name: String,
+    total: u64,
+    done: u64,
+    step: u64,
+    next: u64,
+}
+
+/// Starts a task of `total` steps, 0 if it's unknown how many it takes.
+pub fn task(name: &str, total: u64) -> Task {
+    let (progress, name) = CURRENT.with(|current| match &*current.borrow() {
+        Some((progress, label)) => (Arc::clone(progress), format!("{} {}", label, name)),
+        None => (Arc::new(Silent) as Arc<dyn Progress>, name.to_string()),
+    });
+
+    progress.start(&name, total);
+
+    // reporting every single step of a loop with millions of iterations would
+    // take longer than the loop itself, without a total the steps between
+    // updates double up to a limit
+    let step = (total / 100).max(1);
+    Task { progress, name, total, done: 0, step, next: step }
+}
+
+impl Task {
+    pub fn inc(&mut self, steps: u64) {
+        self.done += steps;
+        if self.done >= self.next {
+            self.progress.update(&self.name, self.done);
+            if self.total == 0 {
+                self.step = (self.step * 2).min(MAX_UNKNOWN_STEP);
+            }
+            self.next = self.done + self.step;
+        }
+    }
+}
+
+impl Drop for Task {
+    fn drop(&mut self) {
+        self.progress.finish(&self.name);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Progress for Recorder {
+        fn start(&self, task: &str, total: u64) {
+            self.events.lock().unwrap().push(format!("start {} {}", task, total));
+        }
+
+        fn update(&self, task: &str, done: u64) {
+            self.events.lock().unwrap().push(format!("update {} {}", task, done));
+        }
+
+        fn finish(&self, task: &str) {
+            self.events.lock().unwrap().push(format!("finish {}", task));
+        }
+    }
+
+    #[test]
+    pub fn tasks_report_to_the_reporter_of_their_scope() {
+        let recorder = Arc::new(Recorder::default());
+        let progress: Arc<dyn Progress> = recorder.clone();
+
+        scope(&progress, "23/2", || {
+            let mut task = task("Moving cups", 1000);
+            for _ in 0..1000 {
+                task.inc(1);
+            }
+        });
+
+        // outside of a scope nothing is reported
+        task("Moving cups", 10).inc(10);
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(102, events.len());
+        assert_eq!("start 23/2 Moving cups 1000", events[0]);
+        assert_eq!("update 23/2 Moving cups 10", events[1]);
+        assert_eq!("update 23/2 Moving cups 1000", events[100]);
+        assert_eq!("finish 23/2 Moving cups", events[101]);
+    }
+
+    #[test]
+    pub fn tasks_without_a_total_report_less_and_less_often() {
+        let recorder = Arc::new(Recorder::default());
+        let progress: Arc<dyn Progress> = recorder.clone();
+
+        scope(&progress, "22/2", || {
+            let mut task = task("Playing rounds", 0);
+            for _ in 0..1_000_000 {
+                task.inc(1);
+            }
+        });
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!("update 22/2 Playing rounds 1", events[1]);
+        assert_eq!("update 22/2 Playing rounds 3", events[2]);
+        assert_eq!("update 22/2 Playing rounds 7", events[3]);
+        // 16 doubling updates, then one every 65536 steps
+        assert_eq!(2 + 16 + (1_000_000 - 65535) / 65536, events.len());
+    }
+
+    #[test]
+    pub fn reporters_are_removed_when_a_scope_panics() {
+        let recorder = Arc::new(Recorder::default());
+        let progress: Arc<dyn Progress> = recorder.clone();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| scope(&progress, "23/2", || panic!("solver failed"))));
+        assert!(result.is_err());
+
+        task("Moving cups", 10).inc(10);
+        assert!(recorder.events.lock().unwrap().is_empty());
+    }
+}
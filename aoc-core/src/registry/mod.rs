@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use super::solution::{Erased, Puzzle, Solution};
+
+/// A registered year: where its sources and known answers are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Year {
+    pub year: u16,
+    /// The `src` directory of the year's crate, where `aoc new` adds days
+    pub src: &'static str,
+    pub answers: &'static str,
+}
+
+/// A registered day: its solution and the input bundled with the binary.
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub input: &'static str,
+    pub puzzle: Box<dyn Puzzle>,
+}
+
+/// All known solutions, keyed by year and day.
+pub struct Registry {
+    years: BTreeMap<u16, Year>,
+    entries: BTreeMap<(u16, u8), Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry { years: BTreeMap::new(), entries: BTreeMap::new() }
+    }
+
+    pub fn add_year(&mut self, year: Year) {
+        self.years.insert(year.year, year);
+    }
+
+    pub fn register<S: Solution + 'static>(&mut self, year: u16, day: u8, input: &'static str) {
+        let puzzle = Box::new(Erased::<S>::new());
+        self.entries.insert((year, day), Entry { year, day, input, puzzle });
+    }
+
+    pub fn year(&self, year: u16) -> Option<&Year> {
+        self.years.get(&year)
+    }
+
+    /// The year days are picked from if no year is given.
+    pub fn latest_year(&self) -> Option<u16> {
+        self.years.keys().next_back().copied()
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    /// All registered days of the given year, ordered by day.
+    pub fn days(&self, year: u16) -> impl Iterator<Item = &Entry> {
+        self.entries.range((year, 0)..=(year, u8::MAX)).map(|(_, e)| e)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::answer::Answer;
+    use super::super::utils::Error;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = String;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::Text(input.clone()))
+        }
+
+        fn part2(_input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::None)
+        }
+    }
+
+    #[test]
+    pub fn days_are_kept_apart_by_year() {
+        let mut registry = Registry::new();
+        registry.add_year(Year { year: 2020, src: "src", answers: "answers.txt" });
+        registry.add_year(Year { year: 2015, src: "src", answers: "answers.txt" });
+        registry.register::<Echo>(2020, 2, "b");
+        registry.register::<Echo>(2020, 1, "a");
+        registry.register::<Echo>(2015, 1, "c");
+
+        assert_eq!(vec![1, 2], registry.days(2020).map(|e| e.day).collect::<Vec<_>>());
+        assert_eq!("c", registry.get(2015, 1).unwrap().input);
+        assert_eq!(Some(2020), registry.latest_year());
+    }
+
+    #[test]
+    pub fn unknown_year_has_no_days() {
+        let registry = Registry::new();
+
+        assert_eq!(0, registry.days(2019).count());
+        assert!(registry.get(2019, 1).is_none());
+        assert!(registry.year(2019).is_none());
+        assert_eq!(None, registry.latest_year());
+    }
+}
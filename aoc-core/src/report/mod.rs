@@ -0,0 +1,181 @@
+use std::time::Duration;
+use super::answer::Answer;
+use super::memory::Usage;
+
+/// How the results of `run` are printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// "year/day/part: answer" for humans, errors go to stderr
+    Text,
+    /// One JSON object per line
+    Json,
+    Csv,
+}
+
+/// The outcome of one part, or of parsing the input if `part` is `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub answer: Option<Answer>,
+    pub parse_time: Duration,
+    pub solve_time: Option<Duration>,
+    pub error: Option<String>,
+    /// Only known if the binary counts allocations, see `memory::Counting`
+    pub parse_memory: Option<Usage>,
+    pub solve_memory: Option<Usage>,
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn json_value<T, F: Fn(&T) -> String>(value: &Option<T>, f: F) -> String {
+    value.as_ref().map(f).unwrap_or_else(|| "null".to_string())
+}
+
+/// Quotes a CSV field if needed, see RFC 4180.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_value<T, F: Fn(&T) -> String>(value: &Option<T>, f: F) -> String {
+    value.as_ref().map(f).unwrap_or_default()
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        format!("{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{},\"error\":{},\
+            \"parse_bytes\":{},\"parse_allocs\":{},\"parse_peak\":{},\"solve_bytes\":{},\"solve_allocs\":{},\"solve_peak\":{}}}",
+            self.year,
+            self.day,
+            json_value(&self.part, |p| p.to_string()),
+            json_value(&self.answer, |a| json_string(&a.to_string())),
+            self.parse_time.as_nanos(),
+            json_value(&self.solve_time, |t| t.as_nanos().to_string()),
+            json_value(&self.error, |e| json_string(e)),
+            json_value(&self.parse_memory, |m| m.allocated.to_string()),
+            json_value(&self.parse_memory, |m| m.allocations.to_string()),
+            json_value(&self.parse_memory, |m| m.peak.to_string()),
+            json_value(&self.solve_memory, |m| m.allocated.to_string()),
+            json_value(&self.solve_memory, |m| m.allocations.to_string()),
+            json_value(&self.solve_memory, |m| m.peak.to_string()))
+    }
+
+    pub fn to_csv(&self) -> String {
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            csv_value(&self.part, |p| p.to_string()),
+            csv_value(&self.answer, |a| csv_field(&a.to_string())),
+            self.parse_time.as_nanos().to_string(),
+            csv_value(&self.solve_time, |t| t.as_nanos().to_string()),
+            csv_value(&self.error, |e| csv_field(e)),
+            csv_value(&self.parse_memory, |m| m.allocated.to_string()),
+            csv_value(&self.parse_memory, |m| m.allocations.to_string()),
+            csv_value(&self.parse_memory, |m| m.peak.to_string()),
+            csv_value(&self.solve_memory, |m| m.allocated.to_string()),
+            csv_value(&self.solve_memory, |m| m.allocations.to_string()),
+            csv_value(&self.solve_memory, |m| m.peak.to_string()),
+        ].join(",")
+    }
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+
+    /// Printed once before the first record.
+    pub fn print_header(&self) {
+        if *self == Format::Csv {
+            println!("year,day,part,answer,parse_ns,solve_ns,error,parse_bytes,parse_allocs,parse_peak,solve_bytes,solve_allocs,solve_peak");
+        }
+    }
+
+    pub fn print(&self, record: &Record) {
+        match self {
+            Format::Text => match (&record.part, &record.answer, &record.error) {
+                (None, _, Some(err)) => eprintln!("{}/{}: Unable to parse input: {}", record.year, record.day, err),
+                (Some(part), _, Some(err)) => eprintln!("{}/{}/{}: Error occurred: {}", record.year, record.day, part, err),
+                (Some(part), Some(answer), None) if answer.is_multiline() => println!("{}/{}/{}:\n{}", record.year, record.day, part, answer),
+                (Some(part), Some(answer), None) => println!("{}/{}/{}: {}", record.year, record.day, part, answer),
+                _ => {},
+            },
+            Format::Json => println!("{}", record.to_json()),
+            Format::Csv => println!("{}", record.to_csv()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solved() -> Record {
+        Record {
+            year: 2020,
+            day: 21,
+            part: Some(2),
+            answer: Some(Answer::from("a,b")),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Some(Duration::from_nanos(200)),
+            error: None,
+            parse_memory: Some(Usage { allocated: 4096, allocations: 3, peak: 2048 }),
+            solve_memory: None,
+        }
+    }
+
+    fn failed() -> Record {
+        Record {
+            year: 2020,
+            day: 4,
+            part: None,
+            answer: None,
+            parse_time: Duration::from_nanos(10),
+            solve_time: None,
+            error: Some("Missing \"byr\"".to_string()),
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    #[test]
+    pub fn records_are_printed_as_json() {
+        assert_eq!(concat!(r#"{"year":2020,"day":21,"part":2,"answer":"a,b","parse_ns":1500,"solve_ns":200,"error":null,"#,
+            r#""parse_bytes":4096,"parse_allocs":3,"parse_peak":2048,"solve_bytes":null,"solve_allocs":null,"solve_peak":null}"#), solved().to_json());
+        assert_eq!(concat!(r#"{"year":2020,"day":4,"part":null,"answer":null,"parse_ns":10,"solve_ns":null,"error":"Missing \"byr\"","#,
+            r#""parse_bytes":null,"parse_allocs":null,"parse_peak":null,"solve_bytes":null,"solve_allocs":null,"solve_peak":null}"#), failed().to_json());
+    }
+
+    #[test]
+    pub fn records_are_printed_as_csv() {
+        assert_eq!(r#"2020,21,2,"a,b",1500,200,,4096,3,2048,,,"#, solved().to_csv());
+        assert_eq!(r#"2020,4,,,10,,"Missing ""byr""",,,,,,"#, failed().to_csv());
+    }
+}
@@ -0,0 +1,167 @@
+use std::any::Any;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use super::cancel;
+use super::cli::Selection;
+use super::memory::{self, Usage};
+use super::pool;
+use super::progress::{self, Progress};
+use super::registry::Registry;
+use super::report::Record;
+use super::utils::Error;
+
+/// A part to solve, or a day whose input couldn't be parsed.
+enum Job {
+    Failed(Record),
+    Solve {
+        day: u8,
+        part: u8,
+        parse_time: Duration,
+        parse_memory: Option<Usage>,
+        input: Arc<dyn Any + Send + Sync>,
+    },
+}
+
+/// Runs `f` within `timeout`, reporting progress as `label` and measuring how
+/// long it takes and what it allocates.
+pub(crate) fn measured<R, F: FnOnce() -> R>(timeout: Option<Duration>, progress: &Arc<dyn Progress>, label: &str, f: F) -> (Duration, Option<Usage>, R) {
+    let start = Instant::now();
+    let (result, usage) = memory::measure(|| {
+        cancel::with_timeout(timeout, || progress::scope(progress, label, f))
+    });
+    (start.elapsed(), usage, result)
+}
+
+/// Parses the inputs of the selected days and solves their parts on `jobs`
+/// threads, handing the records to `output` ordered by day and part.
+///
+/// Parsing a day's input and solving each part may take up to `timeout`, but
+/// only solvers that call `cancel::checkpoint` notice running out of time.
+/// Long running solvers report how far they are to `progress`.
+pub fn run<F: FnMut(Record)>(registry: Arc<Registry>, selection: &Selection, jobs: usize, timeout: Option<Duration>, progress: Arc<dyn Progress>, mut output: F) -> Result<(), Error> {
+    let year = selection.year;
+    let mut inputs = Vec::new();
+    for day in &selection.days {
+        let entry = registry.get(year, *day)
+            .ok_or(Error::new(&format!("Day {} of {} is not solved yet", day, year)))?;
+        // a day whose input can't be read fails on its own, like one whose
+        // input can't be parsed
+        inputs.push((*day, selection.input.read(year, *day, entry.input).map_err(|e| e.to_string())));
+    }
+
+    // all inputs are parsed first, so the parts of one day can be solved in
+    // parallel as well
+    let mut parsed = Vec::new();
+    let puzzles = Arc::clone(&registry);
+    let reporter = Arc::clone(&progress);
+    pool::map_ordered(inputs, jobs, move |(day, input)| {
+        let input = match input {
+            Ok(input) => input,
+            Err(error) => return (Duration::default(), None, Err(error)),
+        };
+        let puzzle = &puzzles.get(year, day).expect("Day is registered").puzzle;
+        let label = format!("{}/{}", year, day);
+        let (parse_time, parse_memory, result) = measured(timeout, &reporter, &label, || puzzle.parse(&input));
+        (parse_time, parse_memory, result.map(Arc::from).map_err(|e| e.to_string()))
+    }, |result| parsed.push(result));
+
+    let mut queue = Vec::new();
+    for (day, result) in selection.days.iter().zip(parsed) {
+        let failed = |parse_time, parse_memory, error| Job::Failed(Record {
+            year, day: *day, part: None, answer: None, parse_time, solve_time: None, error: Some(error),
+            parse_memory, solve_memory: None,
+        });
+
+        match result {
+            Ok((parse_time, parse_memory, Ok(input))) => {
+                for part in &selection.parts {
+                    queue.push(Job::Solve { day: *day, part: *part, parse_time, parse_memory, input: Arc::clone(&input) });
+                }
+            },
+            Ok((parse_time, parse_memory, Err(error))) => queue.push(failed(parse_time, parse_memory, error)),
+            Err(error) => queue.push(failed(Duration::default(), None, error)),
+        }
+    }
+
+    let jobs_info = queue.iter()
+        .map(|job| match job {
+            Job::Failed(record) => (record.day, None, record.parse_time, record.parse_memory),
+            Job::Solve { day, part, parse_time, parse_memory, .. } => (*day, Some(*part), *parse_time, *parse_memory),
+        })
+        .collect::<Vec<_>>();
+
+    let mut records = jobs_info.into_iter();
+    pool::map_ordered(queue, jobs, move |job| match job {
+        Job::Failed(record) => record,
+        Job::Solve { day, part, parse_time, parse_memory, input } => {
+            let puzzle = &registry.get(year, day).expect("Day is registered").puzzle;
+            let label = format!("{}/{}/{}", year, day, part);
+            let (solve_time, solve_memory, result) = measured(timeout, &progress, &label, || puzzle.solve(part, input.as_ref()));
+            let solve_time = Some(solve_time);
+
+            match result {
+                Ok(answer) => Record {
+                    year, day, part: Some(part), answer: Some(answer), parse_time, solve_time, error: None,
+                    parse_memory, solve_memory,
+                },
+                Err(err) => Record {
+                    year, day, part: Some(part), answer: None, parse_time, solve_time, error: Some(err.to_string()),
+                    parse_memory, solve_memory,
+                },
+            }
+        },
+    }, |result| {
+        let (day, part, parse_time, parse_memory) = records.next().expect("One record per job");
+        match result {
+            Ok(record) => output(record),
+            Err(error) => output(Record {
+                year, day, part, answer: None, parse_time, solve_time: None, error: Some(error),
+                parse_memory, solve_memory: None,
+            }),
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::answer::Answer;
+    use super::super::solution::Solution;
+    use super::super::utils::{parse, parse_lines, InputSource};
+
+    /// Sums the numbers of its input.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(parse_lines(input, parse::<i64>)?)
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::from(input.iter().sum::<i64>()))
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::from(input.iter().product::<i64>()))
+        }
+    }
+
+    #[test]
+    pub fn unreadable_inputs_fail_their_day_only() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>(2020, 1, "1\n2\n3\n");
+        registry.register::<Sum>(2020, 2, "4\n");
+        let selection = Selection { year: 2020, days: vec![1, 2], parts: vec![1], input: InputSource::File("missing.txt".to_string()) };
+
+        let mut records = Vec::new();
+        run(Arc::new(registry), &selection, 2, None, Arc::new(progress::Silent), |record| records.push(record)).unwrap();
+
+        assert_eq!(vec![1, 2], records.iter().map(|r| r.day).collect::<Vec<_>>());
+        assert!(records.iter().all(|r| r.part.is_none() && r.answer.is_none()));
+        assert!(records[0].error.as_ref().unwrap().starts_with("Unable to read input from 'missing.txt'"), "{:?}", records[0].error);
+    }
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::utils::Error;
+
+/// The skeleton every new day starts from, `Template` is replaced by the name
+/// of the day.
+const TEMPLATE: &str = include_str!("../template/mod.rs");
+
+/// Placeholder answers of the example, its tests fail until they are filled in.
+const EXPECTED: &str = include_str!("../template/example.expected");
+
+/// Marks where the `mod` and `register` lines of new days are inserted in the
+/// lib.rs of a year's crate.
+const MARKER: &str = "// aoc new adds new days above this line";
+
+/// Inserts one line in front of each marker, indented like the marker.
+fn insert_at_markers(contents: &str, lines: &[String]) -> Result<String, Error> {
+    let mut lines = lines.iter();
+    let mut out = String::new();
+
+    for line in contents.lines() {
+        if line.trim() == MARKER {
+            let new_line = lines.next()
+                .ok_or(Error::new("More markers than lines to insert"))?;
+            let indent = &line[..line.len() - line.trim_start().len()];
+            out.push_str(&format!("{}{}\n", indent, new_line));
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    if lines.next().is_some() {
+        return Err(Error::new(&format!("Missing marker '{}'", MARKER)));
+    }
+
+    Ok(out)
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map_err(|e| Error::new(&format!("Unable to read '{}': {}", path.display(), e)))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents)
+        .map_err(|e| Error::new(&format!("Unable to write '{}': {}", path.display(), e)))
+}
+
+/// Creates `dayN` in the `src` of a year's crate from the template, with empty
+/// input and example files and placeholder answers of the example, and adds it
+/// to lib.rs and its `register`.
+///
+/// Returns the files that were created or changed.
+pub fn new_day(src: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    let name = format!("day{}", day);
+    let dir = src.join(&name);
+    if dir.exists() {
+        return Err(Error::new(&format!("'{}' already exists", dir.display())));
+    }
+
+    // everything is prepared first, so nothing is written if a marker is missing
+    let lib_path = src.join("lib.rs");
+    let lib = insert_at_markers(&read(&lib_path)?, &[
+        format!("pub mod {};", name),
+        format!("registry.register::<{}::Day{}>(YEAR, {}, {}::INPUT);", name, day, day, name),
+    ])?;
+
+    let data = dir.join("data");
+    fs::create_dir_all(&data)
+        .map_err(|e| Error::new(&format!("Unable to create '{}': {}", data.display(), e)))?;
+
+    let files = vec![
+        (dir.join("mod.rs"), TEMPLATE.replace("Template", &format!("Day{}", day))),
+        (data.join("input.txt"), String::new()),
+        (data.join("example.txt"), String::new()),
+        (data.join("example.expected"), EXPECTED.to_string()),
+        (lib_path, lib),
+    ];
+
+    for (path, contents) in &files {
+        write(path, contents)?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    pub fn lines_are_inserted_at_markers() {
+        let contents = format!("mod day1;\n{}\n\nfn main() {{\n    day1();\n    {}\n}}\n", MARKER, MARKER);
+        let lines = vec!["mod day2;".to_string(), "day2();".to_string()];
+
+        let expected = format!("mod day1;\nmod day2;\n{}\n\nfn main() {{\n    day1();\n    day2();\n    {}\n}}\n", MARKER, MARKER);
+        assert_eq!(expected, insert_at_markers(&contents, &lines).unwrap());
+        assert!(insert_at_markers("mod day1;\n", &lines).is_err());
+    }
+
+    #[test]
+    pub fn new_day_is_created_and_registered() {
+        let src = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), format!("pub mod day1;\n{}\n\npub fn register() {{\n    {}\n}}\n", MARKER, MARKER)).unwrap();
+
+        let files = new_day(&src, 2).unwrap();
+
+        assert_eq!(5, files.len());
+        assert!(fs::read_to_string(src.join("day2").join("mod.rs")).unwrap().contains("impl Solution for Day2"));
+        assert!(src.join("day2").join("data").join("example.txt").exists());
+        assert!(fs::read_to_string(src.join("day2").join("data").join("example.expected")).unwrap().contains("\n1 ?\n"));
+        let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+        assert!(lib.starts_with(&format!("pub mod day1;\npub mod day2;\n{}\n", MARKER)));
+        assert!(lib.contains("    registry.register::<day2::Day2>(YEAR, 2, day2::INPUT);\n"));
+        assert!(new_day(&src, 2).is_err());
+
+        fs::remove_dir_all(&src).unwrap();
+    }
+}
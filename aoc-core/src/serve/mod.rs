@@ -0,0 +1,247 @@
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use super::pool::panic_message;
+use super::progress::{Progress, Silent};
+use super::registry::Registry;
+use super::report::{json_string, Record};
+use super::runner::measured;
+use super::utils::Error;
+
+/// Where the server listens if no address is given.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8020";
+
+/// Inputs are a few KiB usually, anything much bigger is a mistake.
+const MAX_INPUT: u64 = 16 * 1024 * 1024;
+
+/// A status code and the JSON to answer a request with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response { status, body: format!("{{\"error\":{}}}", json_string(message)) }
+    }
+}
+
+/// Picks year, day and part from "/2020/day/1/part/2".
+fn route(path: &str) -> Option<(u16, u8, u8)> {
+    let segments = path.trim_end_matches('/').split('/').collect::<Vec<_>>();
+    match segments[..] {
+        ["", year, "day", day, "part", part @ ("1" | "2")] => {
+            Some((year.parse().ok()?, day.parse().ok()?, part.parse().ok()?))
+        },
+        _ => None,
+    }
+}
+
+/// Solves the registered puzzles for HTTP requests.
+pub struct Service {
+    registry: Arc<Registry>,
+    /// The longest a request may take, requests can ask for less with
+    /// `?timeout=SECS`
+    timeout: Option<Duration>,
+}
+
+impl Service {
+    pub fn new(registry: Arc<Registry>, timeout: Option<Duration>) -> Self {
+        Service { registry, timeout }
+    }
+
+    fn timeout(&self, query: &str) -> Result<Option<Duration>, Error> {
+        let requested = match query.split('&').find_map(|pair| pair.strip_prefix("timeout=")) {
+            Some(secs) => match secs.parse::<f64>().map(Duration::try_from_secs_f64) {
+                Ok(Ok(duration)) if !duration.is_zero() => duration,
+                _ => return Err(Error::new(&format!("Not a number of seconds: '{}'", secs))),
+            },
+            None => return Ok(self.timeout),
+        };
+
+        Ok(Some(self.timeout.map_or(requested, |max| max.min(requested))))
+    }
+
+    /// Answers `POST /{year}/day/{day}/part/{part}` with the input as body
+    /// with the same JSON as `run --format json`. Inputs that can't be parsed
+    /// or solved are answered with 422, timeouts with 504.
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> Response {
+        let (path, query) = match url.find('?') {
+            Some(i) => (&url[..i], &url[i + 1..]),
+            None => (url, ""),
+        };
+
+        let (year, day, part) = match route(path) {
+            Some(route) => route,
+            None => return Response::error(404, "Not found, the solvers are at POST /{year}/day/{day}/part/{part}"),
+        };
+        if method != "POST" {
+            return Response::error(405, "Only POST is allowed, with the input as body");
+        }
+        let timeout = match self.timeout(query) {
+            Ok(timeout) => timeout,
+            Err(err) => return Response::error(400, &err.to_string()),
+        };
+        let entry = match self.registry.get(year, day) {
+            Some(entry) => entry,
+            None => return Response::error(404, &format!("Day {} of {} is not solved yet", day, year)),
+        };
+
+        let progress: Arc<dyn Progress> = Arc::new(Silent);
+        let label = format!("{}/{}/{}", year, day, part);
+        let (parse_time, parse_memory, parsed) = measured(timeout, &progress, &label, || entry.puzzle.parse(body));
+        let mut record = Record {
+            year, day, part: Some(part), answer: None, parse_time, solve_time: None, error: None,
+            parse_memory, solve_memory: None,
+        };
+
+        let result = parsed.and_then(|input| {
+            let (solve_time, solve_memory, result) = measured(timeout, &progress, &label, || entry.puzzle.solve(part, input.as_ref()));
+            record.solve_time = Some(solve_time);
+            record.solve_memory = solve_memory;
+            result
+        });
+
+        let status = match result {
+            Ok(answer) => {
+                record.answer = Some(answer);
+                200
+            },
+            Err(err) => {
+                record.error = Some(err.to_string());
+                match err {
+                    Error::Timeout(_) => 504,
+                    _ => 422,
+                }
+            },
+        };
+
+        Response { status, body: record.to_json() }
+    }
+}
+
+/// Reads the request, lets `service` answer it and logs it to stderr.
+fn respond(service: &Service, mut request: tiny_http::Request) {
+    let method = request.method().to_string();
+    let url = request.url().to_string();
+
+    let mut body = String::new();
+    let response = match request.as_reader().take(MAX_INPUT + 1).read_to_string(&mut body) {
+        Err(_) => Response::error(400, "The input is not UTF-8"),
+        Ok(len) if len as u64 > MAX_INPUT => Response::error(413, "The input is too large"),
+        // a solver that panics takes down this request only
+        Ok(_) => panic::catch_unwind(AssertUnwindSafe(|| service.handle(&method, &url, &body)))
+            .unwrap_or_else(|payload| Response::error(500, &panic_message(payload.as_ref()))),
+    };
+
+    eprintln!("{} {} {}", method, url, response.status);
+    let content_type = tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("Header is valid");
+    let _ = request.respond(tiny_http::Response::from_string(response.body)
+        .with_status_code(response.status)
+        .with_header(content_type));
+}
+
+/// Listens on `addr` and answers requests on `threads` threads, until the
+/// process is stopped.
+pub fn serve(service: Service, addr: &str, threads: usize) -> Result<(), Error> {
+    let server = tiny_http::Server::http(addr)
+        .map_err(|e| Error::new(&format!("Unable to listen on '{}': {}", addr, e)))?;
+    eprintln!("Listening on http://{}", server.server_addr());
+    let server = Arc::new(server);
+    let service = Arc::new(service);
+
+    let handles = (0..threads.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let service = Arc::clone(&service);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(&service, request);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::answer::Answer;
+    use super::super::cancel;
+    use super::super::solution::Solution;
+    use super::super::utils::{parse, parse_lines};
+
+    /// Sums the numbers of its input, the second part never ends.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(parse_lines(input, parse::<i64>)?)
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::from(input.iter().sum::<i64>()))
+        }
+
+        fn part2(_input: &Self::Input) -> Result<Answer, Error> {
+            loop {
+                cancel::checkpoint()?;
+            }
+        }
+    }
+
+    fn service() -> Service {
+        let mut registry = Registry::new();
+        registry.register::<Sum>(2020, 1, "");
+        Service::new(Arc::new(registry), Some(Duration::from_secs(5)))
+    }
+
+    #[test]
+    pub fn paths_are_routed_to_year_day_and_part() {
+        assert_eq!(Some((2020, 1, 2)), route("/2020/day/1/part/2"));
+        assert_eq!(Some((2015, 25, 1)), route("/2015/day/25/part/1/"));
+        assert_eq!(None, route("/2020/day/1/part/3"));
+        assert_eq!(None, route("/2020/day/1"));
+        assert_eq!(None, route("/"));
+    }
+
+    #[test]
+    pub fn inputs_are_solved_with_timings() {
+        let response = service().handle("POST", "/2020/day/1/part/1", "1\n2\n3\n");
+
+        assert_eq!(200, response.status);
+        assert!(response.body.starts_with(r#"{"year":2020,"day":1,"part":1,"answer":"6","parse_ns":"#), "{}", response.body);
+        assert!(response.body.contains(r#""error":null"#));
+    }
+
+    #[test]
+    pub fn failures_are_answered_with_their_status() {
+        let service = service();
+
+        assert_eq!(404, service.handle("POST", "/2020/day/2/part/1", "1").status);
+        assert_eq!(404, service.handle("POST", "/answers", "1").status);
+        assert_eq!(405, service.handle("GET", "/2020/day/1/part/1", "").status);
+        assert_eq!(400, service.handle("POST", "/2020/day/1/part/1?timeout=soon", "1").status);
+        assert_eq!(400, service.handle("POST", "/2020/day/1/part/1?timeout=1e30", "1").status);
+
+        let response = service.handle("POST", "/2020/day/1/part/1", "1\nx\n");
+        assert_eq!(422, response.status);
+        assert!(response.body.contains("\"answer\":null"));
+
+        let response = service.handle("POST", "/2020/day/1/part/2?timeout=0.05", "1\n");
+        assert_eq!(504, response.status);
+        assert!(response.body.contains("Timed out after 0.05s"), "{}", response.body);
+    }
+}
@@ -0,0 +1,63 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use super::answer::Answer;
+use super::utils::Error;
+
+/// A day's puzzle, split into parsing the input and solving both parts based
+/// on the parsed input.
+pub trait Solution {
+    /// Shared between the threads that solve the parts, see `runner::run`.
+    type Input: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, Error>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, Error>;
+}
+
+/// Object safe counterpart of `Solution` so the solutions of all days can be
+/// kept in one place, see `registry::Registry`.
+///
+/// The parsed input is passed around as `Any` and it's up to the
+/// implementation to turn it back into its `Solution::Input`.
+pub trait Puzzle: Send + Sync {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, Error>;
+
+    fn solve(&self, part: u8, input: &dyn Any) -> Result<Answer, Error>;
+}
+
+/// Wraps a `Solution` into a `Puzzle`.
+pub struct Erased<S> {
+    solution: PhantomData<fn() -> S>,
+}
+
+impl<S: Solution> Erased<S> {
+    pub fn new() -> Self {
+        Erased { solution: PhantomData }
+    }
+}
+
+impl<S: Solution> Default for Erased<S> {
+    fn default() -> Self {
+        Erased::new()
+    }
+}
+
+impl<S: Solution> Puzzle for Erased<S> {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, Error> {
+        let parsed = S::parse(input)?;
+        Ok(Box::new(parsed))
+    }
+
+    fn solve(&self, part: u8, input: &dyn Any) -> Result<Answer, Error> {
+        let input = input.downcast_ref::<S::Input>()
+            .ok_or(Error::new("Parsed input belongs to a different puzzle"))?;
+
+        match part {
+            1 => S::part1(input),
+            2 => S::part2(input),
+            p => Err(Error::new(&format!("There is no part {}", p))),
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use regex::Regex;
+use super::inputs::{self, Config, USER_AGENT};
+use super::utils::Error;
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint in which direction
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The last answer was submitted too recently, nothing was checked
+    Wait(Duration),
+    /// The part is solved already or isn't unlocked yet
+    AlreadySolved,
+    /// A response that isn't understood, with the text of the page
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the HTML page the server answers with.
+    pub fn parse(html: &str) -> Verdict {
+        lazy_static!{
+            static ref RE_WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+            static ref RE_ARTICLE: Regex = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+            static ref RE_TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+        }
+
+        if html.contains("That's the right answer") {
+            Verdict::Correct
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if html.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if html.contains("You gave an answer too recently") {
+            let secs = RE_WAIT.captures(html)
+                .map(|cap| {
+                    let minutes = cap.get(1).map_or(0, |m| m.as_str().parse::<u64>().unwrap_or(0));
+                    minutes * 60 + cap[2].parse::<u64>().unwrap_or(0)
+                })
+                .unwrap_or(60);
+            Verdict::Wait(Duration::from_secs(secs))
+        } else if html.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            let text = RE_ARTICLE.captures(html)
+                .map_or(html, |cap| cap.get(1).unwrap().as_str());
+            let text = RE_TAG.replace_all(text, "");
+            Verdict::Unknown(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+    }
+
+    /// The name of a verdict in the history, `None` for verdicts that say
+    /// nothing about the answer.
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("right"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::TooHigh => Some("high"),
+            Verdict::TooLow => Some("low"),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "right" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "high" => Some(Verdict::TooHigh),
+            "low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer"),
+            Verdict::Wrong => write!(f, "That's not the right answer"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Verdict::Wait(left) => write!(f, "An answer was submitted too recently, wait {}s before trying again", left.as_secs()),
+            Verdict::AlreadySolved => write!(f, "The part is solved already or not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "Unknown response: {}", text),
+        }
+    }
+}
+
+/// A submitted answer and its verdict.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer that got a definitive verdict, so known wrong answers are
+/// never submitted twice.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// Reads the history, a missing file is treated as an empty one.
+    pub fn load(path: &Path) -> Result<History, Error> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::new(&format!("Unable to read guesses from '{}': {}", path.display(), e)))?;
+        History::parse(&contents)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let error = |e: std::io::Error| Error::new(&format!("Unable to save guesses to '{}': {}", path.display(), e));
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        fs::write(path, self.to_string()).map_err(error)
+    }
+
+    /// Parses lines of the form "day part verdict answer".
+    pub fn parse(s: &str) -> Result<History, Error> {
+        let mut history = History::default();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+            let invalid = || Error::new(&format!("Invalid guesses line: '{}'", line));
+            let fields = line.splitn(4, ' ').collect::<Vec<_>>();
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+
+            history.guesses.push(Guess {
+                day: fields[0].parse::<u8>().map_err(|_| invalid())?,
+                part: fields[1].parse::<u8>().map_err(|_| invalid())?,
+                verdict: Verdict::from_name(fields[2]).ok_or_else(invalid)?,
+                answer: fields[3].to_string(),
+            });
+        }
+
+        Ok(history)
+    }
+
+    /// Remembers the verdict, unless it says nothing about the answer.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: &Verdict) {
+        if verdict.name().is_some() {
+            self.guesses.push(Guess { day, part, answer: answer.to_string(), verdict: verdict.clone() });
+        }
+    }
+
+    pub fn guesses(&self, day: u8, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| g.day == day && g.part == part)
+    }
+
+    /// Refuses answers that are known to be wrong: answers that were wrong
+    /// before, numbers beyond a bound learned from a too high or too low
+    /// answer, and anything once the part is solved.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), Error> {
+        let number = answer.parse::<i128>().ok();
+
+        for guess in self.guesses(day, part) {
+            let refused = |reason: &str| Err(Error::new(&format!("Not submitting {} for day {} part {}: {}", answer, day, part, reason)));
+            let bound = guess.answer.parse::<i128>().ok();
+
+            match (&guess.verdict, number, bound) {
+                (Verdict::Correct, _, _) if guess.answer == answer => return refused("it is the right answer already"),
+                (Verdict::Correct, _, _) => return refused(&format!("the part is solved already with {}", guess.answer)),
+                (_, _, _) if guess.answer == answer => return refused(&format!("it was submitted before ({})", guess.verdict)),
+                (Verdict::TooHigh, Some(n), Some(high)) if n >= high => return refused(&format!("{} is too high already", high)),
+                (Verdict::TooLow, Some(n), Some(low)) if n <= low => return refused(&format!("{} is too low already", low)),
+                _ => {},
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part verdict answer")?;
+
+        for guess in &self.guesses {
+            let verdict = guess.verdict.name().expect("Only definitive verdicts are recorded");
+            writeln!(f, "{} {} {} {}", guess.day, guess.part, verdict, guess.answer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, for form bodies.
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Submits answers with the session of the input `Config`, keeping the
+/// history of guesses next to the cached inputs.
+pub struct Client {
+    config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Client { config }
+    }
+
+    /// Where the guesses of a year are kept.
+    pub fn history_path(&self, year: u16) -> PathBuf {
+        self.config.cache_dir.join(year.to_string()).join("guesses.txt")
+    }
+
+    /// Submits `answer` unless the history knows it is wrong, and records the
+    /// verdict.
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let answer = answer.trim();
+        if answer.is_empty() || answer.contains('\n') {
+            return Err(Error::new(&format!("Not an answer that can be submitted: '{}'", answer)));
+        }
+
+        let path = self.history_path(year);
+        let mut history = History::load(&path)?;
+        history.check(day, part, answer)?;
+
+        let session = self.config.session.as_ref()
+            .ok_or(Error::new("There is no session to submit with, set AOC_SESSION or save the session cookie in the config directory"))?;
+
+        inputs::wait_for_turn(self.config.min_interval);
+
+        let url = format!("{}/{}/day/{}/answer", self.config.base_url.trim_end_matches('/'), year, day);
+        let response = ureq::post(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .set("Content-Type", "application/x-www-form-urlencoded")
+            .timeout_connect(10_000)
+            .timeout_read(30_000)
+            .send_string(&format!("level={}&answer={}", part, encode(answer)));
+
+        if let Some(err) = response.synthetic_error() {
+            return Err(Error::new(&format!("Unable to submit answer of day {} to '{}': {}", day, url, err)));
+        }
+
+        let verdict = match response.status() {
+            200 => response.into_string()
+                .map(|html| Verdict::parse(&html))
+                .map_err(|e| Error::new(&format!("Unable to read the verdict from '{}': {}", url, e)))?,
+            400 | 401 | 403 => return Err(Error::new(&format!(
+                "Submitting the answer of day {} was refused ({}), the session is probably invalid or expired", day, response.status()))),
+            status => return Err(Error::new(&format!("Unable to submit answer of day {} to '{}': {} {}",
+                day, url, status, response.status_text()))),
+        };
+
+        if verdict.name().is_some() {
+            history.record(day, part, answer, &verdict);
+            history.save(&path)?;
+        }
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use super::*;
+    use super::super::inputs::stub;
+
+    #[test]
+    pub fn verdicts_are_read_from_the_page() {
+        let page = |text: &str| format!("<html><main><article><p>{}</p></article></main></html>", text);
+
+        assert_eq!(Verdict::Correct, Verdict::parse(&page("That's the right answer!  You are one gold star closer.")));
+        assert_eq!(Verdict::TooHigh, Verdict::parse(&page("That's not the right answer; your answer is too high.")));
+        assert_eq!(Verdict::TooLow, Verdict::parse(&page("That's not the right answer; your answer is too low.")));
+        assert_eq!(Verdict::Wrong, Verdict::parse(&page("That's not the right answer.  If you're stuck, ...")));
+        assert_eq!(Verdict::Wait(Duration::from_secs(94)),
+            Verdict::parse(&page("You gave an answer too recently; ...  You have 1m 34s left to wait.")));
+        assert_eq!(Verdict::Wait(Duration::from_secs(7)),
+            Verdict::parse(&page("You gave an answer too recently; ...  You have 7s left to wait.")));
+        assert_eq!(Verdict::AlreadySolved,
+            Verdict::parse(&page("You don't seem to be solving the right level.  Did you already complete it?")));
+        assert_eq!(Verdict::Unknown("Something new".to_string()),
+            Verdict::parse(&page("Something <b>new</b>")));
+    }
+
+    #[test]
+    pub fn known_wrong_answers_are_refused() {
+        let history = History::parse("\
+            # day part verdict answer\n\
+            1 1 high 1000\n\
+            1 1 low 10\n\
+            1 2 wrong abc\n\
+            2 1 right 42\n").unwrap();
+
+        assert!(history.check(1, 1, "500").is_ok());
+        assert!(history.check(1, 1, "1000").is_err());
+        assert!(history.check(1, 1, "1001").is_err());
+        assert!(history.check(1, 1, "10").is_err());
+        assert!(history.check(1, 1, "-3").is_err());
+        assert!(history.check(1, 2, "abc").is_err());
+        assert!(history.check(1, 2, "abd").is_ok());
+        assert!(history.check(2, 1, "42").is_err());
+        assert!(history.check(2, 1, "43").is_err());
+        assert!(history.check(2, 2, "42").is_ok());
+
+        assert_eq!(History::parse(&history.to_string()).unwrap(), history);
+    }
+
+    #[test]
+    pub fn answers_are_posted_and_remembered() {
+        let (url, requests) = stub::serve(vec![
+            (200, "<article><p>That's not the right answer; your answer is too low.</p></article>"),
+            (200, "<article><p>You gave an answer too recently; You have 42s left to wait.</p></article>"),
+            (200, "<article><p>That's the right answer!</p></article>"),
+        ]);
+        let cache_dir = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let client = Client::new(Config {
+            base_url: url,
+            session: Some("53cr3t".to_string()),
+            cache_dir: cache_dir.clone(),
+            min_interval: Duration::from_millis(0),
+        });
+
+        assert_eq!(Verdict::TooLow, client.submit(2020, 1, 2, "12 & 3").unwrap());
+        let request = requests.recv().unwrap();
+        assert_eq!("POST /2020/day/1/answer HTTP/1.1", request.line);
+        assert_eq!("session=53cr3t", request.cookie);
+        assert_eq!("level=2&answer=12%20%26%203", request.body);
+
+        // refused without asking the server
+        assert!(client.submit(2020, 1, 2, "12 & 3").is_err());
+
+        assert_eq!(Verdict::Wait(Duration::from_secs(42)), client.submit(2020, 1, 2, "241861950").unwrap());
+        assert_eq!(Verdict::Correct, client.submit(2020, 1, 2, "241861950").unwrap());
+        assert_eq!(2, History::load(&client.history_path(2020)).unwrap().guesses(1, 2).count());
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    pub fn server_errors_are_not_blamed_on_the_session() {
+        let (url, _requests) = stub::serve(vec![(500, "Internal Server Error"), (403, "Forbidden")]);
+        let cache_dir = env::temp_dir().join(format!("aoc-submit-errors-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let client = Client::new(Config {
+            base_url: url,
+            session: Some("53cr3t".to_string()),
+            cache_dir: cache_dir.clone(),
+            min_interval: Duration::from_millis(0),
+        });
+
+        let err = client.submit(2020, 1, 1, "42").unwrap_err().to_string();
+        assert!(err.contains(": 500 ") && !err.contains("session"), "{}", err);
+        let err = client.submit(2020, 1, 1, "42").unwrap_err().to_string();
+        assert!(err.contains("session is probably invalid"), "{}", err);
+
+        let _ = fs::remove_dir_all(&cache_dir);
+    }
+}
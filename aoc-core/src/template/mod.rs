@@ -0,0 +1,46 @@
+use super::utils::{parse, parse_lines, Error, ParseError};
+use super::answer::Answer;
+use super::solution::Solution;
+
+pub const INPUT: &str = include_str!("./data/input.txt");
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(input, parse::<i32>)
+}
+
+pub struct Template;
+
+impl Solution for Template {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(_input: &Self::Input) -> Result<Answer, Error> {
+        Ok(Answer::None)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Answer, Error> {
+        Ok(Answer::None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("./data/example.txt");
+
+    #[test]
+    pub fn example_1_1() {
+        let input = Template::parse(EXAMPLE).unwrap();
+        assert_eq!(Answer::None, Template::part1(&input).unwrap());
+    }
+
+    #[test]
+    pub fn example_2_1() {
+        let input = Template::parse(EXAMPLE).unwrap();
+        assert_eq!(Answer::None, Template::part2(&input).unwrap());
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+
+/// A position on a grid as `(x, y)`, with `y` growing downwards. Positions
+/// are signed so the neighbors of cells on the edge can be expressed.
+pub type Pos = (i64, i64);
+
+/// Which cells count as neighbors of a cell on a grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighborhood {
+    /// Up, right, down and left
+    Four,
+    /// The four above and the diagonals
+    Eight,
+}
+
+impl Neighborhood {
+    /// The steps from a cell to its neighbors.
+    pub fn offsets(&self) -> &'static [Pos] {
+        match self {
+            Neighborhood::Four => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            Neighborhood::Eight => &[(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)],
+        }
+    }
+}
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with the cell at each position computed by `f`.
+    pub fn from_fn<F: FnMut(Pos) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x as i64, y as i64)))
+            .map(&mut f)
+            .collect();
+
+        Grid { width, height, cells }
+    }
+
+    /// A grid of `rows`, if they are all equally long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn offset(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.1 as usize * self.width + pos.0 as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos`, nothing outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.offset(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cell at `pos` on a grid that repeats endlessly in all directions.
+    pub fn get_wrapped(&self, (x, y): Pos) -> &T {
+        &self[(x.rem_euclid(self.width as i64), y.rem_euclid(self.height as i64))]
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x as i64, y as i64)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks doesn't allow a size of 0, but there are no cells then anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The neighbors of `pos` that are on the grid.
+    pub fn neighbors(&self, (x, y): Pos, neighborhood: Neighborhood) -> impl Iterator<Item = Pos> + '_ {
+        neighborhood.offsets()
+            .iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(move |pos| self.contains(*pos))
+    }
+
+    /// How many cells `f` holds for.
+    pub fn count<F: Fn(&T) -> bool>(&self, f: F) -> usize {
+        self.cells.iter().filter(|cell| f(cell)).count()
+    }
+
+    /// A grid of the same size with each cell computed from the cell at the
+    /// same position.
+    pub fn map<U, F: FnMut(Pos, &T) -> U>(&self, mut f: F) -> Grid<U> {
+        let cells = self.iter().map(|(pos, cell)| f(pos, cell)).collect();
+        Grid { width: self.width, height: self.height, cells }
+    }
+
+    /// The cells as text, one line per row.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self {
+        Grid { width, height, cells: vec![cell; width * height] }
+    }
+
+    pub fn column(&self, x: usize) -> Vec<T> {
+        self.rows().map(|row| row[x].clone()).collect()
+    }
+
+    /// The grid turned clockwise by a quarter `turns` times.
+    pub fn rotate(&self, turns: usize) -> Self {
+        match turns % 4 {
+            0 => self.clone(),
+            turns => {
+                let height = self.height as i64;
+                let turned = Grid::from_fn(self.height, self.width, |(x, y)| self[(y, height - 1 - x)].clone());
+                turned.rotate(turns - 1)
+            },
+        }
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_x(&self) -> Self {
+        let width = self.width as i64;
+        Grid::from_fn(self.width, self.height, |(x, y)| self[(width - 1 - x, y)].clone())
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_y(&self) -> Self {
+        let height = self.height as i64;
+        Grid::from_fn(self.width, self.height, |(x, y)| self[(x, height - 1 - y)].clone())
+    }
+
+    /// All 8 ways to turn and mirror the grid, starting with the grid as is.
+    pub fn orientations(&self) -> Vec<Self> {
+        (0..4)
+            .map(|turns| self.rotate(turns))
+            .flat_map(|turned| {
+                let flipped = turned.flip_x();
+                vec![turned, flipped]
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A position on a sparse grid, in as many dimensions as it likes.
+pub trait Position: Copy + Eq + Hash {
+    /// The positions next to this one, diagonals included.
+    fn neighbors(&self) -> Vec<Self>;
+
+    /// The smallest coordinates of both positions on each axis.
+    fn min(&self, other: &Self) -> Self;
+
+    /// The largest coordinates of both positions on each axis.
+    fn max(&self, other: &Self) -> Self;
+}
+
+impl Position for (i64, i64) {
+    fn neighbors(&self) -> Vec<Self> {
+        Neighborhood::Eight.offsets().iter().map(|(dx, dy)| (self.0 + dx, self.1 + dy)).collect()
+    }
+
+    fn min(&self, other: &Self) -> Self {
+        (self.0.min(other.0), self.1.min(other.1))
+    }
+
+    fn max(&self, other: &Self) -> Self {
+        (self.0.max(other.0), self.1.max(other.1))
+    }
+}
+
+impl Position for (i64, i64, i64) {
+    fn neighbors(&self) -> Vec<Self> {
+        let mut neighbors = Vec::with_capacity(26);
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy, dz) != (0, 0, 0) {
+                        neighbors.push((self.0 + dx, self.1 + dy, self.2 + dz));
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    fn min(&self, other: &Self) -> Self {
+        (self.0.min(other.0), self.1.min(other.1), self.2.min(other.2))
+    }
+
+    fn max(&self, other: &Self) -> Self {
+        (self.0.max(other.0), self.1.max(other.1), self.2.max(other.2))
+    }
+}
+
+impl Position for (i64, i64, i64, i64) {
+    fn neighbors(&self) -> Vec<Self> {
+        let mut neighbors = Vec::with_capacity(80);
+        for dw in -1..=1 {
+            for dz in -1..=1 {
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if (dx, dy, dz, dw) != (0, 0, 0, 0) {
+                            neighbors.push((self.0 + dx, self.1 + dy, self.2 + dz, self.3 + dw));
+                        }
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    fn min(&self, other: &Self) -> Self {
+        (self.0.min(other.0), self.1.min(other.1), self.2.min(other.2), self.3.min(other.3))
+    }
+
+    fn max(&self, other: &Self) -> Self {
+        (self.0.max(other.0), self.1.max(other.1), self.2.max(other.2), self.3.max(other.3))
+    }
+}
+
+/// Cells at some positions of an unbounded grid, for grids that grow or
+/// aren't square.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<P: Position, T> {
+    cells: HashMap<P, T>,
+}
+
+impl<P: Position, T> SparseGrid<P, T> {
+    pub fn new() -> Self {
+        SparseGrid { cells: HashMap::new() }
+    }
+
+    pub fn get(&self, pos: &P) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &P) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    /// Puts `cell` at `pos`, returning the cell that was there.
+    pub fn insert(&mut self, pos: P, cell: T) -> Option<T> {
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn remove(&mut self, pos: &P) -> Option<T> {
+        self.cells.remove(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells with their positions, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&P, &T)> {
+        self.cells.iter()
+    }
+
+    /// How many cells `f` holds for.
+    pub fn count<F: Fn(&T) -> bool>(&self, f: F) -> usize {
+        self.cells.values().filter(|cell| f(cell)).count()
+    }
+
+    /// The smallest and the largest coordinates of any cell on each axis,
+    /// nothing if there are no cells.
+    pub fn bounds(&self) -> Option<(P, P)> {
+        self.cells.keys().fold(None, |bounds, pos| match bounds {
+            None => Some((*pos, *pos)),
+            Some((min, max)) => Some((min.min(pos), max.max(pos))),
+        })
+    }
+}
+
+impl<P: Position> SparseGrid<P, bool> {
+    /// The next generation of a game of life on the cells that are `true`,
+    /// i.e. alive. `rule` decides from whether a cell is alive and how many of
+    /// its neighbors are whether it is alive in the next generation. Only the
+    /// living cells are kept, as cells without any living neighbors can't come
+    /// alive anyway.
+    pub fn life<F: Fn(bool, usize) -> bool>(&self, rule: F) -> Self {
+        let alive = |pos: &P| self.get(pos) == Some(&true);
+        let candidates = self.iter()
+            .filter(|(_, alive)| **alive)
+            .flat_map(|(pos, _)| pos.neighbors().into_iter().chain(Some(*pos)))
+            .collect::<HashSet<_>>();
+
+        candidates.into_iter()
+            .filter(|pos| rule(alive(pos), pos.neighbors().iter().filter(|n| alive(n)).count()))
+            .map(|pos| (pos, true))
+            .collect()
+    }
+}
+
+impl<P: Position, T> Default for SparseGrid<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Position, T> FromIterator<(P, T)> for SparseGrid<P, T> {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        SparseGrid { cells: iter.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::from_rows(rows.iter().map(|row| row.chars().collect()).collect()).unwrap()
+    }
+
+    #[test]
+    pub fn neighbors_stay_on_the_grid() {
+        let grid = grid(&["ab", "cd", "ef"]);
+
+        assert_eq!(vec![(1, 0), (0, 1)], grid.neighbors((0, 0), Neighborhood::Four).collect::<Vec<_>>());
+        assert_eq!(5, grid.neighbors((1, 1), Neighborhood::Eight).count());
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(&'f', grid.get_wrapped((-1, 5)));
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+
+    #[test]
+    pub fn grids_turn_and_mirror() {
+        let grid = grid(&["ab", "cd", "ef"]);
+
+        assert_eq!("eca\nfdb\n", grid.rotate(1).to_string());
+        assert_eq!("fe\ndc\nba\n", grid.rotate(2).to_string());
+        assert_eq!(grid.rotate(3), grid.rotate(1).flip_x().flip_y());
+        assert_eq!("ba\ndc\nfe\n", grid.flip_x().to_string());
+        assert_eq!("ef\ncd\nab\n", grid.flip_y().render(|c| *c));
+        assert_eq!(vec!['b', 'd', 'f'], grid.column(1));
+
+        let orientations = grid.orientations();
+        assert_eq!(grid, orientations[0]);
+        assert_eq!(8, orientations.iter().collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    pub fn sparse_grids_play_life() {
+        // a blinker turns from horizontal to vertical and back
+        let blinker = vec![(0, 1), (1, 1), (2, 1)].into_iter()
+            .map(|pos| (pos, true))
+            .collect::<SparseGrid<(i64, i64), _>>();
+        let rule = |alive, n| n == 3 || (alive && n == 2);
+
+        let turned = blinker.life(rule);
+        assert_eq!(Some(((1, 0), (1, 2))), turned.bounds());
+        assert_eq!(blinker, turned.life(rule));
+        assert_eq!(3, turned.count(|alive| *alive));
+    }
+}
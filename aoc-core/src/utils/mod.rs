@@ -0,0 +1,577 @@
+use std::any;
+use std::num::{NonZeroUsize, ParseIntError, ParseFloatError};
+use std::str::FromStr;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::time::Duration;
+use super::inputs::{Config, Inputs};
+use self::grid::Grid;
+
+pub mod grid;
+
+#[allow(dead_code)]
+pub fn read_file(filename: &str) -> String {
+  let mut f = File::open(filename).expect("file not found");
+
+  let mut contents = String::new();
+  f.read_to_string(&mut contents)
+    .expect("something went wrong reading the file");
+
+  contents
+}
+
+/// Where the puzzle input is read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+  /// The input.txt that is compiled into the binary
+  Bundled,
+  /// The user's own input from the cache, downloaded if it isn't cached yet
+  Cache,
+  Stdin,
+  File(String),
+}
+
+impl InputSource {
+  /// Maps a command line argument to an input source, '-' being stdin.
+  pub fn from_arg(arg: Option<&str>) -> InputSource {
+    match arg {
+      None => InputSource::Bundled,
+      Some("-") => InputSource::Stdin,
+      Some(path) => InputSource::File(path.to_string()),
+    }
+  }
+
+  pub fn read(&self, year: u16, day: u8, bundled: &str) -> Result<String, Error> {
+    match self {
+      InputSource::Bundled => Ok(bundled.to_string()),
+      InputSource::Cache => Inputs::new(Config::from_env()?).get(year, day),
+      InputSource::Stdin => {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)
+          .map_err(|e| Error::new(&format!("Unable to read input from stdin: {}", e)))?;
+        Ok(contents)
+      },
+      InputSource::File(path) => {
+        let mut contents = String::new();
+        File::open(path)
+          .and_then(|mut f| f.read_to_string(&mut contents))
+          .map_err(|e| Error::new(&format!("Unable to read input from '{}': {}", path, e)))?;
+        Ok(contents)
+      },
+    }
+  }
+}
+
+pub fn preprocess_input(input: &str) -> Vec<&str> {
+  Input::new(input).lines().collect()
+}
+
+/// A puzzle input as the parsers see it, no matter whether it has Windows
+/// line endings, trailing whitespace or no final newline. All views are
+/// sliced from the original text, so errors can still be located in it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Input<'a> {
+  /// The whole input, which errors are located in
+  source: &'a str,
+  text: &'a str,
+}
+
+impl<'a> Input<'a> {
+  pub fn new(text: &'a str) -> Self {
+    // editors on Windows like to start files with a byte order mark
+    Input { source: text, text: text.strip_prefix('\u{feff}').unwrap_or(text) }
+  }
+
+  pub fn text(&self) -> &'a str {
+    self.text
+  }
+
+  /// Every line without line ending and trailing whitespace, blank ones
+  /// included.
+  fn all_lines(&self) -> impl Iterator<Item = &'a str> {
+    self.text.split('\n').map(|line| line.trim_end())
+  }
+
+  /// The lines that aren't blank.
+  pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+    self.all_lines().filter(|line| !line.is_empty())
+  }
+
+  /// The only line of an input that fits on one line.
+  pub fn line(&self) -> Result<&'a str, ParseError> {
+    let mut lines = self.lines();
+    let line = lines.next().ok_or(ParseError::new("The input is empty"))?;
+
+    match lines.next() {
+      Some(extra) => Err(ParseError::new("Expected a single line").locate(self.source, extra)),
+      None => Ok(line),
+    }
+  }
+
+  /// The groups of lines separated by blank lines, as inputs of their own.
+  pub fn blocks(&self) -> Vec<Input<'a>> {
+    let mut blocks = vec![];
+    let mut block: Option<(&str, &str)> = None;
+
+    for line in self.all_lines() {
+      match (line.is_empty(), block) {
+        (true, Some((first, last))) => {
+          blocks.push(self.span(first, last));
+          block = None;
+        },
+        (true, None) => {},
+        (false, Some((first, _))) => block = Some((first, line)),
+        (false, None) => block = Some((line, line)),
+      }
+    }
+    if let Some((first, last)) = block {
+      blocks.push(self.span(first, last));
+    }
+
+    blocks
+  }
+
+  /// From the start of `first` to the end of `last`, both sliced from the text.
+  fn span(&self, first: &'a str, last: &'a str) -> Input<'a> {
+    let start = first.as_ptr() as usize - self.text.as_ptr() as usize;
+    let end = last.as_ptr() as usize + last.len() - self.text.as_ptr() as usize;
+    Input { source: self.source, text: &self.text[start..end] }
+  }
+
+  /// The lines as a grid of chars, all of them as long as the first one.
+  pub fn grid(&self) -> Result<Grid<char>, ParseError> {
+    self.grid_with(Ok)
+  }
+
+  /// The lines as a grid of the cells `f` makes of each char.
+  pub fn grid_with<T, F>(&self, f: F) -> Result<Grid<T>, ParseError>
+    where F: Fn(char) -> Result<T, ParseError>
+  {
+    let mut rows: Vec<Vec<T>> = vec![];
+
+    for line in self.lines() {
+      let row = line.char_indices()
+        .map(|(i, c)| f(c).map_err(|e| e.locate(self.source, &line[i..i + c.len_utf8()])))
+        .collect::<Result<Vec<_>, _>>()?;
+
+      match rows.first() {
+        Some(first) if first.len() != row.len() => {
+          return Err(ParseError::new(&format!("Expected a row of {} cells, found {}", first.len(), row.len()))
+            .locate(self.source, line));
+        },
+        _ => rows.push(row),
+      }
+    }
+
+    Ok(Grid::from_rows(rows).expect("Rows are equally long"))
+  }
+}
+
+/// The error type of everything in this crate, from parsing an input over
+/// solving a puzzle to running the command line.
+#[derive(Debug)]
+pub enum Error {
+  /// The input is malformed
+  Parse(ParseError),
+  /// The input is fine, but the puzzle can't be solved with it, e.g. because
+  /// no combination of numbers adds up to 2020
+  Solve(String),
+  /// Anything that isn't about a puzzle, like unreadable files or invalid
+  /// command line arguments
+  Run(String),
+  /// The solver ran out of time, see `cancel::checkpoint`
+  Timeout(Duration),
+  /// The solver was cancelled before it was done
+  Cancelled,
+}
+
+impl Error {
+  pub fn new(s: &str) -> Error {
+    Error::Run(s.to_string())
+  }
+
+  pub fn solve(s: &str) -> Error {
+    Error::Solve(s.to_string())
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::Parse(e) => write!(f, "{}", e),
+      Error::Solve(what) => write!(f, "{}", what),
+      Error::Run(what) => write!(f, "{}", what),
+      Error::Timeout(timeout) => write!(f, "Timed out after {}s", timeout.as_secs_f64()),
+      Error::Cancelled => write!(f, "Cancelled"),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Error::Parse(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+/// Where in the input a `ParseError` occurred.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+  /// Line number, starting at 1
+  pub line: usize,
+  /// Column in chars, starting at 1
+  pub column: usize,
+  /// The whole line the error occurred in
+  pub source: String,
+  /// Length of the offending text in chars
+  pub len: usize,
+}
+
+impl Location {
+  /// Locates `span` in `input`, preferably by its address if it was sliced
+  /// from `input`, otherwise by searching for it.
+  pub fn find(input: &str, span: &str) -> Option<Location> {
+    let start = input.as_ptr() as usize;
+    let address = span.as_ptr() as usize;
+
+    let offset = if address >= start && address + span.len() <= start + input.len() {
+      address - start
+    } else {
+      input.find(span)?
+    };
+
+    let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = input[offset..].find('\n').map(|i| offset + i).unwrap_or(input.len());
+    let source = input[line_start..line_end].trim_end_matches('\r');
+    let column = input[line_start..offset].chars().count() + 1;
+    let remaining = source.chars().count() + 1 - column;
+    let len = span.lines().next().unwrap_or("").chars().count().min(remaining).max(1);
+
+    Some(Location {
+      line: input[..offset].matches('\n').count() + 1,
+      column,
+      source: source.to_string(),
+      len,
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+  pub what: String,
+  pub location: Option<Location>,
+  /// The text that couldn't be parsed, used to find the column once the error
+  /// is located in its line.
+  pub text: Option<String>,
+  /// Where the text was, to tell it apart from the same text earlier in its
+  /// line
+  address: Option<NonZeroUsize>,
+  pub cause: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl ParseError {
+  pub fn new(s: &str) -> ParseError {
+    let what = s.to_string();
+    ParseError { what, location: None, text: None, address: None, cause: None }
+  }
+
+  pub fn with_cause<E: std::error::Error + Send + Sync + 'static>(mut self, cause: E) -> ParseError {
+    self.cause = Some(Box::new(cause));
+    self
+  }
+
+  pub fn with_text(mut self, text: &str) -> ParseError {
+    self.text = Some(text.to_string());
+    self.address = NonZeroUsize::new(text.as_ptr() as usize);
+    self
+  }
+
+  /// Locates the error at `span` of `input`, or more precisely at its
+  /// offending text if that is part of `span`. Errors that are already located
+  /// keep their location, as the innermost one is the most precise.
+  pub fn locate(mut self, input: &str, span: &str) -> ParseError {
+    if self.location.is_some() {
+      return self;
+    }
+
+    let start = span.as_ptr() as usize;
+    let span = match (&self.text, self.address.map(NonZeroUsize::get)) {
+      // the text is still where it was found if it was sliced from the span
+      (Some(text), Some(address)) if address >= start && span.get(address - start..address - start + text.len()) == Some(text.as_str()) => {
+        &span[address - start..address - start + text.len()]
+      },
+      (Some(text), _) => span.find(text.as_str()).map(|i| &span[i..i + text.len()]).unwrap_or(span),
+      (None, _) => span,
+    };
+    self.location = Location::find(input, span);
+    self
+  }
+}
+
+/// Renders like a compiler diagnostic, e.g.
+///
+/// ```text
+/// Unable to parse 'x3' as i32: invalid digit found in string
+/// --> 3:5
+///   |
+/// 3 | acc x3
+///   |     ^^
+/// ```
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.what)?;
+    if let Some(cause) = &self.cause {
+      write!(f, ": {}", cause)?;
+    }
+
+    if let Some(location) = &self.location {
+      let gutter = " ".repeat(location.line.to_string().len());
+      write!(f, "\n{}--> {}:{}", gutter, location.line, location.column)?;
+      write!(f, "\n{} |", gutter)?;
+      write!(f, "\n{} | {}", location.line, location.source)?;
+      write!(f, "\n{} | {}{}", gutter, " ".repeat(location.column - 1), "^".repeat(location.len))?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for ParseError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match &self.cause {
+      Some(cause) => Some(cause.as_ref()),
+      None => None,
+    }
+  }
+}
+
+impl From<ParseIntError> for ParseError {
+  fn from(error: ParseIntError) -> Self {
+    ParseError::new("Unable to parse integer").with_cause(error)
+  }
+}
+
+impl From<ParseFloatError> for ParseError {
+  fn from(error: ParseFloatError) -> Self {
+    ParseError::new("Unable to parse float").with_cause(error)
+  }
+}
+
+/// Like `str::parse`, but the error keeps the text that couldn't be parsed.
+pub fn parse<T>(s: &str) -> Result<T, ParseError>
+  where T: FromStr, T::Err: std::error::Error + Send + Sync + 'static
+{
+  s.parse::<T>()
+    .map_err(|e| ParseError::new(&format!("Unable to parse '{}' as {}", s, any::type_name::<T>())).with_cause(e).with_text(s))
+}
+
+/// Parses every non-empty line of `input` with `f`, errors are located in the
+/// line they occurred in.
+pub fn parse_lines<T, F>(input: &str, f: F) -> Result<Vec<T>, ParseError>
+  where F: Fn(&str) -> Result<T, ParseError>
+{
+  Input::new(input)
+    .lines()
+    .map(|v| f(v).map_err(|e| e.locate(input, v)))
+    .collect::<Result<Vec<_>, ParseError>>()
+}
+
+/// A part of a `scan` pattern.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Piece<'p> {
+  Text(&'p str),
+  /// A field of exactly as many chars, or up to the next text
+  Field(Option<usize>),
+}
+
+fn pieces(pattern: &str) -> Vec<Piece<'_>> {
+  let mut pieces = vec![];
+  let mut rest = pattern;
+
+  while let Some(open) = rest.find('{') {
+    if open > 0 {
+      pieces.push(Piece::Text(&rest[..open]));
+    }
+    let close = rest[open..].find('}').unwrap_or_else(|| panic!("Unclosed '{{' in pattern '{}'", pattern)) + open;
+    let width = match &rest[open + 1..close] {
+      "" => None,
+      width => Some(width.parse().unwrap_or_else(|_| panic!("Not a field width in pattern '{}': '{}'", pattern, width))),
+    };
+    if let (Some(Piece::Field(None)), Piece::Field(_)) = (pieces.last(), Piece::Field(width)) {
+      panic!("Fields in pattern '{}' need text in between", pattern);
+    }
+    pieces.push(Piece::Field(width));
+    rest = &rest[close + 1..];
+  }
+  if !rest.is_empty() {
+    pieces.push(Piece::Text(rest));
+  }
+
+  pieces
+}
+
+/// The first `n` chars of `s`, or all of it if it is shorter.
+fn prefix(s: &str, n: usize) -> &str {
+  &s[..s.char_indices().nth(n).map_or(s.len(), |(i, _)| i)]
+}
+
+/// Matches `s` against a scanf-like `pattern`, in which `{}` is a field up to
+/// the text following it in the pattern, or up to the end, and `{N}` a field
+/// of exactly N chars. Everything else has to match as is. Returns the fields
+/// as slices of `s`, see `scan!` to parse them as well.
+pub fn scan<'a>(s: &'a str, pattern: &str) -> Result<Vec<&'a str>, ParseError> {
+  let pieces = pieces(pattern);
+  let mut fields = vec![];
+  let mut rest = s;
+
+  for (i, piece) in pieces.iter().enumerate() {
+    let len = match *piece {
+      Piece::Text(text) if rest.starts_with(text) => {
+        rest = &rest[text.len()..];
+        continue;
+      },
+      Piece::Text(text) => {
+        return Err(ParseError::new(&format!("Expected '{}'", text)).with_text(prefix(rest, text.chars().count())));
+      },
+      Piece::Field(Some(width)) if rest.chars().count() >= width => prefix(rest, width).len(),
+      Piece::Field(Some(width)) => {
+        return Err(ParseError::new(&format!("Expected {} chars", width)).with_text(rest));
+      },
+      Piece::Field(None) => match pieces.get(i + 1) {
+        Some(Piece::Text(text)) => rest.find(text).ok_or_else(|| ParseError::new(&format!("Expected '{}'", text)).with_text(rest))?,
+        _ => rest.len(),
+      },
+    };
+
+    fields.push(&rest[..len]);
+    rest = &rest[len..];
+  }
+
+  if !rest.is_empty() {
+    return Err(ParseError::new("Unexpected text at the end").with_text(rest));
+  }
+
+  Ok(fields)
+}
+
+/// Matches a line against a `scan` pattern and parses its fields as the given
+/// types, evaluating to a `Result` with a tuple of them. Fields of type `&str`
+/// are taken as they are, all others are parsed with `parse`.
+///
+/// ```
+/// # #[macro_use] extern crate aoc_core;
+/// # fn main() -> Result<(), aoc_core::utils::ParseError> {
+/// let (from, to, letter, password) = scan!("1-3 a: abcde", "{}-{} {1}: {}", u32, u32, char, &str)?;
+/// assert_eq!((1, 3, 'a', "abcde"), (from, to, letter, password));
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! scan {
+  (@fields $iter:ident, ($($done:expr,)*)) => {
+    ($($done,)*)
+  };
+  (@fields $iter:ident, ($($done:expr,)*) & str $(, $($rest:tt)*)?) => {
+    $crate::scan!(@fields $iter, ($($done,)* $iter.next().expect("A type for every field"),) $($($rest)*)?)
+  };
+  (@fields $iter:ident, ($($done:expr,)*) $t:ty $(, $($rest:tt)*)?) => {
+    $crate::scan!(@fields $iter, ($($done,)* $crate::utils::parse::<$t>($iter.next().expect("A type for every field"))?,) $($($rest)*)?)
+  };
+  ($s:expr, $pattern:expr, $($types:tt)+) => {
+    $crate::utils::scan($s, $pattern).and_then(|fields| {
+      let mut fields = fields.into_iter();
+      Ok($crate::scan!(@fields fields, () $($types)+))
+    })
+  };
+}
+
+impl From<ParseError> for Error {
+  fn from(error: ParseError) -> Self {
+    Error::Parse(error)
+  }
+}
+
+impl From<ParseIntError> for Error {
+  fn from(error: ParseIntError) -> Self {
+    Error::Parse(ParseError::from(error))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const INPUT: &str = "nop +0\nacc +1\njmp x4\n";
+
+  #[test]
+  pub fn errors_are_located_at_the_offending_text() {
+    let err = parse_lines(INPUT, |line| parse::<i32>(&line[4..])).unwrap_err();
+
+    assert_eq!(Some(Location { line: 3, column: 5, source: "jmp x4".to_string(), len: 2 }), err.location);
+  }
+
+  #[test]
+  pub fn errors_without_text_are_located_at_the_line() {
+    let err = parse_lines(INPUT, |line| line[4..].parse::<i32>().map_err(ParseError::from)).unwrap_err();
+
+    assert_eq!(Some(Location { line: 3, column: 1, source: "jmp x4".to_string(), len: 6 }), err.location);
+  }
+
+  #[test]
+  pub fn line_endings_and_trailing_whitespace_are_ignored() {
+    let unix = Input::new("a b\nc\n\nd\n");
+    let windows = Input::new("\u{feff}a b \r\nc\t\r\n \r\n\r\nd");
+
+    assert_eq!(unix.lines().collect::<Vec<_>>(), windows.lines().collect::<Vec<_>>());
+    assert_eq!(vec!["a b\nc", "d"], unix.blocks().iter().map(|b| b.text()).collect::<Vec<_>>());
+    assert_eq!(vec![vec!["a b", "c"], vec!["d"]], windows.blocks().iter().map(|b| b.lines().collect::<Vec<_>>()).collect::<Vec<_>>());
+  }
+
+  #[test]
+  pub fn views_are_sliced_from_the_input() {
+    let input = "#.\r\n.#\r\n\r\n##\r\n#\r\n";
+    let blocks = Input::new(input).blocks();
+
+    assert_eq!(Grid::from_rows(vec![vec!['#', '.'], vec!['.', '#']]), blocks[0].grid().ok());
+    let err = blocks[0].grid_with(|c| if c == '#' { Ok(true) } else { Err(ParseError::new("Not a tree")) }).unwrap_err();
+    assert_eq!(Some((1, 2)), err.location.map(|l| (l.line, l.column)));
+    let err = blocks[1].grid().unwrap_err();
+    assert_eq!(Some(5), err.location.map(|l| l.line));
+    let err = parse_lines(input, parse::<i32>).unwrap_err();
+    assert_eq!(Some(1), err.location.map(|l| l.line));
+
+    assert_eq!("0,3,6", Input::new("0,3,6\r\n").line().unwrap());
+    assert!(Input::new("0,3,6\n1\n").line().is_err());
+  }
+
+  #[test]
+  pub fn lines_are_scanned_into_typed_fields() {
+    assert_eq!(vec!["mem", "8", "11"], scan("mem[8] = 11", "{}[{}] = {}").unwrap());
+    assert_eq!((3, "faded blue"), scan!("3 faded blue bags", "{} {} bags", usize, &str).unwrap());
+    assert_eq!(('F', 10), scan!("F10", "{1}{}", char, i32).unwrap());
+  }
+
+  #[test]
+  pub fn scan_errors_are_located_at_the_mismatch() {
+    let located = |input: &str, pattern: &str| {
+      parse_lines(input, |line| scan!(line, pattern, char, u32)).unwrap_err().location.map(|l| (l.line, l.column, l.len))
+    };
+
+    // the second 'a' is meant, not the first one
+    assert_eq!(Some((2, 3, 1)), located("a 1\na a\n", "{1} {}"));
+    assert_eq!(Some((1, 1, 3)), located("a 1\n", "{} - {}"));
+    assert_eq!(Some((1, 2, 2)), located("a-1\n", "{1} = {}"));
+    assert_eq!(Some((1, 4, 1)), located("a 1x\n", "{1} {1}"));
+  }
+
+  #[test]
+  pub fn errors_are_rendered_like_diagnostics() {
+    let err = parse_lines(INPUT, |line| parse::<i32>(&line[4..])).unwrap_err();
+
+    assert_eq!("Unable to parse 'x4' as i32: invalid digit found in string\n --> 3:5\n  |\n3 | jmp x4\n  |     ^^", err.to_string());
+  }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use super::answer::Answer;
+use super::utils::Error;
+
+/// Identifies an input by its contents, so answers recorded for one input are
+/// never checked against another one.
+///
+/// Uses 64 bit FNV-1a, which unlike `DefaultHasher` is guaranteed to be the
+/// same across compiler versions.
+pub fn fingerprint(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+/// Known good answers, keyed by day, part and input fingerprint.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8, String), String>,
+}
+
+/// The result of checking one answer against the answers file.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Match,
+    Mismatch { expected: String },
+    Missing,
+}
+
+/// Answers are stored on a single line, so line breaks in grids are escaped.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => { out.push('\n'); chars.next(); },
+            ('\\', Some('\\')) => { out.push('\\'); chars.next(); },
+            (c, _) => out.push(c),
+        }
+    }
+
+    out
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Answers { entries: BTreeMap::new() }
+    }
+
+    /// Reads the answers file, a missing file is treated as an empty one.
+    pub fn load(path: &str) -> Result<Answers, Error> {
+        if !Path::new(path).exists() {
+            return Ok(Answers::new());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::new(&format!("Unable to read answers from '{}': {}", path, e)))?;
+        Answers::parse(&contents)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.to_string())
+            .map_err(|e| Error::new(&format!("Unable to save answers to '{}': {}", path, e)))
+    }
+
+    /// Parses lines of the form "day part fingerprint answer".
+    pub fn parse(s: &str) -> Result<Answers, Error> {
+        let mut answers = Answers::new();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+            let invalid = || Error::new(&format!("Invalid answers line: '{}'", line));
+            let fields = line.splitn(4, ' ').collect::<Vec<_>>();
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+
+            let day = fields[0].parse::<u8>().map_err(|_| invalid())?;
+            let part = fields[1].parse::<u8>().map_err(|_| invalid())?;
+            answers.entries.insert((day, part, fields[2].to_string()), unescape(fields[3]));
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&String> {
+        self.entries.get(&(day, part, input.to_string()))
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, input: &str, answer: &Answer) {
+        self.entries.insert((day, part, input.to_string()), answer.to_string());
+    }
+
+    pub fn check(&self, day: u8, part: u8, input: &str, answer: &Answer) -> Outcome {
+        match self.get(day, part, input) {
+            Some(expected) if *expected == answer.to_string() => Outcome::Match,
+            Some(expected) => Outcome::Mismatch { expected: expected.clone() },
+            None => Outcome::Missing,
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part input answer")?;
+
+        for ((day, part, input), answer) in &self.entries {
+            writeln!(f, "{} {} {} {}", day, part, input, escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn answers_are_checked_per_input() {
+        let mut answers = Answers::new();
+        answers.record(1, 1, &fingerprint("1721\n979\n"), &Answer::from(514579));
+
+        assert_eq!(Outcome::Match, answers.check(1, 1, &fingerprint("1721\n979\n"), &Answer::from(514579)));
+        assert_eq!(Outcome::Mismatch { expected: "514579".to_string() }, answers.check(1, 1, &fingerprint("1721\n979\n"), &Answer::from(42)));
+        assert_eq!(Outcome::Missing, answers.check(1, 1, &fingerprint("1721\n"), &Answer::from(514579)));
+        assert_eq!(Outcome::Missing, answers.check(1, 2, &fingerprint("1721\n979\n"), &Answer::from(514579)));
+    }
+
+    #[test]
+    pub fn recorded_answers_can_be_read_back() {
+        let mut answers = Answers::new();
+        answers.record(20, 2, "0123456789abcdef", &Answer::from(vec!["#.\\".to_string(), ".#.".to_string()]));
+        answers.record(21, 2, "0123456789abcdef", &Answer::from("a b,c"));
+
+        assert_eq!(answers, Answers::parse(&answers.to_string()).unwrap());
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use super::cancel;
+use super::pool::panic_message;
+use super::solution::Puzzle;
+use super::utils::Error;
+
+/// How often the data directory is looked at if no interval is given.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When a file was modified last and how long it is, as the modification
+/// time alone may not change if a file is written twice within a second.
+type Stamp = (SystemTime, u64);
+
+/// The `.txt` files in `dir` by name.
+fn scan(dir: &Path) -> Result<BTreeMap<String, Stamp>, Error> {
+    let error = |e: std::io::Error| Error::new(&format!("Unable to read '{}': {}", dir.display(), e));
+    let mut files = BTreeMap::new();
+
+    for entry in fs::read_dir(dir).map_err(error)? {
+        let path = entry.map_err(error)?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+            continue;
+        }
+
+        // a file that is removed while scanning shows up as removed next time
+        if let Ok(metadata) = fs::metadata(&path) {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            files.insert(name, (metadata.modified().map_err(error)?, metadata.len()));
+        }
+    }
+
+    Ok(files)
+}
+
+/// The answer of a part, or the error of a part or of parsing the file.
+type Outcome = Result<String, String>;
+
+/// Runs `f`, turning a panic into an error, so a solver that panics on a
+/// half written example doesn't end the session.
+fn guarded<T, F: FnOnce() -> Result<T, Error>>(timeout: Option<Duration>, f: F) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| cancel::with_timeout(timeout, f))) {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => Err(panic_message(payload.as_ref())),
+    }
+}
+
+/// Solves every input in a day's data directory again whenever it changes.
+pub struct Session<'a> {
+    puzzle: &'a dyn Puzzle,
+    dir: PathBuf,
+    parts: Vec<u8>,
+    timeout: Option<Duration>,
+    files: BTreeMap<String, Stamp>,
+    /// The outcomes of the last run, keyed by file and part, or no part if
+    /// the file couldn't be parsed
+    outcomes: BTreeMap<(String, Option<u8>), Outcome>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(puzzle: &'a dyn Puzzle, dir: &Path, parts: &[u8], timeout: Option<Duration>) -> Self {
+        Session {
+            puzzle,
+            dir: dir.to_path_buf(),
+            parts: parts.to_vec(),
+            timeout,
+            files: BTreeMap::new(),
+            outcomes: BTreeMap::new(),
+        }
+    }
+
+    /// Solves the files that were added or changed since the last poll, all
+    /// of them on the first one. Returns the lines to show, nothing if no
+    /// file changed.
+    pub fn poll(&mut self) -> Result<Vec<String>, Error> {
+        let files = scan(&self.dir)?;
+        let changed = files.iter()
+            .filter(|(name, stamp)| self.files.get(*name) != Some(stamp))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let removed = self.files.keys()
+            .filter(|name| !files.contains_key(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.files = files;
+
+        if changed.is_empty() && removed.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut lines = vec![format!("== {}", changed.iter().chain(&removed).cloned().collect::<Vec<_>>().join(", "))];
+        for name in &removed {
+            self.outcomes.retain(|(file, _), _| file != name);
+            lines.push(format!("{}: removed", name));
+        }
+        for name in &changed {
+            lines.extend(self.solve(name));
+        }
+
+        Ok(lines)
+    }
+
+    /// Parses and solves one file, comparing the answers to the last run.
+    fn solve(&mut self, name: &str) -> Vec<String> {
+        let path = self.dir.join(name);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => return vec![format!("{}: Unable to read input: {}", name, e)],
+        };
+
+        let puzzle = self.puzzle;
+        let parsed = match guarded(self.timeout, || puzzle.parse(&input)) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                self.outcomes.retain(|(file, _), _| file != name);
+                self.outcomes.insert((name.to_string(), None), Err(err.clone()));
+                return vec![format!("{}: Unable to parse input: {}", name, err)];
+            },
+        };
+        self.outcomes.remove(&(name.to_string(), None));
+
+        let mut lines = vec![];
+        for part in self.parts.clone() {
+            let outcome = guarded(self.timeout, || puzzle.solve(part, parsed.as_ref()))
+                .map(|answer| answer.to_string());
+            let previous = self.outcomes.insert((name.to_string(), Some(part)), outcome.clone());
+            lines.push(describe(&format!("{}/{}", name, part), &outcome, previous.as_ref()));
+        }
+
+        lines
+    }
+}
+
+/// E.g. "example.txt/2: 8 (was 7)".
+fn describe(label: &str, outcome: &Outcome, previous: Option<&Outcome>) -> String {
+    let change = match previous {
+        None => "".to_string(),
+        Some(previous) if previous == outcome => " (unchanged)".to_string(),
+        Some(Ok(previous)) if !previous.contains('\n') => format!(" (was {})", previous),
+        Some(_) => " (changed)".to_string(),
+    };
+
+    match outcome {
+        Ok(answer) if answer.contains('\n') => format!("{}:{}\n{}", label, change, answer),
+        Ok(answer) => format!("{}: {}{}", label, answer, change),
+        Err(err) => format!("{}: Error occurred: {}{}", label, err, change),
+    }
+}
+
+/// Polls the session every `interval` and hands what changed to `output`,
+/// until polling fails.
+pub fn watch<F: FnMut(&str)>(session: &mut Session, interval: Duration, mut output: F) -> Result<(), Error> {
+    loop {
+        for line in session.poll()? {
+            output(&line);
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use super::*;
+    use super::super::answer::Answer;
+    use super::super::solution::{Erased, Solution};
+
+    /// Counts the lines of its input, the second part panics on empty lines.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input.lines().map(|l| l.to_string()).collect())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::from(input.len()))
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer, Error> {
+            assert!(input.iter().all(|l| !l.is_empty()), "empty line");
+            Ok(Answer::from(input.concat()))
+        }
+    }
+
+    #[test]
+    pub fn changed_files_are_solved_again() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("example.txt"), "a\nb\n").unwrap();
+        fs::write(dir.join("input.txt"), "c\n").unwrap();
+        fs::write(dir.join("example.expected"), "1 2\n").unwrap();
+
+        let puzzle = Erased::<Lines>::new();
+        let mut session = Session::new(&puzzle, &dir, &[1, 2], None);
+
+        assert_eq!(vec![
+            "== example.txt, input.txt", "example.txt/1: 2", "example.txt/2: ab", "input.txt/1: 1", "input.txt/2: c",
+        ], session.poll().unwrap());
+        assert!(session.poll().unwrap().is_empty());
+
+        fs::write(dir.join("example.txt"), "a\nb\n\nd\n").unwrap();
+        assert_eq!(vec![
+            "== example.txt", "example.txt/1: 4 (was 2)", "example.txt/2: Error occurred: Panicked: empty line (was ab)",
+        ], session.poll().unwrap());
+
+        fs::remove_file(dir.join("input.txt")).unwrap();
+        assert_eq!(vec!["== input.txt", "input.txt: removed"], session.poll().unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+extern crate aoc_core;
+
+use aoc_core::memory::{self, Counting};
+
+// the allocator is global to the whole test binary, so the counting one is
+// installed in a binary of its own rather than in the unit tests of aoc-core
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[test]
+pub fn allocations_and_peak_are_counted() {
+    let (sum, usage) = memory::measure(|| {
+        let big = vec![0u8; 1 << 20];
+        drop(big);
+        let small = (1..=16).collect::<Vec<u64>>();
+        small.iter().sum::<u64>()
+    });
+
+    let usage = usage.unwrap();
+    assert_eq!(136, sum);
+    assert_eq!(2, usage.allocations);
+    assert_eq!((1 << 20) + 128, usage.allocated);
+    assert_eq!(1 << 20, usage.peak);
+}
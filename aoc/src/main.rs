@@ -0,0 +1,265 @@
+extern crate aoc_core;
+extern crate advent2020;
+extern crate clap;
+
+use std::path::Path;
+use std::process;
+use std::sync::Arc;
+use clap::ArgMatches;
+use aoc_core::{answer::Answer, bench, cancel, cli, frames, inputs, progress, runner, scaffold, submit, utils, verify, watch};
+use aoc_core::registry::Registry;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc_core::memory::Counting = aoc_core::memory::Counting;
+
+/// All years that have solutions.
+fn registry() -> Registry {
+  let mut registry = Registry::new();
+  advent2020::register(&mut registry);
+  registry
+}
+
+fn latest_year(registry: &Registry) -> Result<u16, utils::Error> {
+  registry.latest_year().ok_or(utils::Error::new("No year is registered"))
+}
+
+fn run(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = Arc::new(registry());
+  let selection = cli::selection(matches, latest_year(&registry)?)?;
+  let format = cli::format(matches)?;
+  let jobs = cli::jobs(matches)?;
+  let timeout = cli::timeout(matches)?;
+  let progress = cli::progress(matches)?;
+  let mut failed = 0;
+
+  format.print_header();
+  runner::run(registry, &selection, jobs, timeout, progress, |record| {
+    if record.error.is_some() {
+      failed += 1;
+    }
+    format.print(&record);
+  })?;
+
+  if failed > 0 {
+    return Err(utils::Error::new(&format!("{} puzzle(s) failed", failed)));
+  }
+
+  Ok(())
+}
+
+fn bench(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let selection = cli::selection(matches, latest_year(&registry)?)?;
+  let options = cli::bench_options(matches)?;
+
+  let previous = match &options.compare {
+    Some(path) => Some(bench::load(path)?),
+    None => None,
+  };
+
+  let mut measurements = Vec::new();
+  for day in &selection.days {
+    let entry = registry.get(selection.year, *day)
+      .ok_or(utils::Error::new(&format!("Day {} of {} is not solved yet", day, selection.year)))?;
+    let input = selection.input.read(selection.year, *day, entry.input)?;
+
+    eprintln!("Benchmarking day {} of {} ({} runs)", day, selection.year, options.runs);
+    measurements.extend(bench::bench_day(entry, &input, &selection.parts, options.runs)?);
+  }
+
+  bench::print_table(&measurements, previous.as_deref());
+
+  if let Some(path) = &options.save {
+    bench::save(path, &measurements)?;
+  }
+
+  Ok(())
+}
+
+fn verify(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let selection = cli::selection(matches, latest_year(&registry)?)?;
+  let options = cli::verify_options(matches);
+  let timeout = cli::timeout(matches)?;
+  let progress = cli::progress(matches)?;
+  let year = registry.year(selection.year)
+    .ok_or(utils::Error::new(&format!("There are no solutions for {}", selection.year)))?;
+  let answers_path = options.answers.as_deref().unwrap_or(year.answers);
+  let mut answers = verify::Answers::load(answers_path)?;
+  let (mut failed, mut missing, mut recorded) = (0, 0, 0);
+
+  for day in &selection.days {
+    let entry = registry.get(selection.year, *day)
+      .ok_or(utils::Error::new(&format!("Day {} of {} is not solved yet", day, selection.year)))?;
+    let input = selection.input.read(selection.year, *day, entry.input)?;
+    let fingerprint = verify::fingerprint(&input);
+
+    let parsed = match cancel::with_timeout(timeout, || {
+      progress::scope(&progress, &format!("{}/{}", selection.year, day), || entry.puzzle.parse(&input))
+    }) {
+      Ok(parsed) => parsed,
+      Err(err) => {
+        eprintln!("{}/{}: Unable to parse input: {}", selection.year, day, err);
+        failed += 1;
+        continue;
+      }
+    };
+
+    for part in &selection.parts {
+      let label = format!("{}/{}/{}", selection.year, day, part);
+      let answer = match cancel::with_timeout(timeout, || {
+        progress::scope(&progress, &label, || entry.puzzle.solve(*part, parsed.as_ref()))
+      }) {
+        Ok(answer) => answer,
+        Err(err) => {
+          eprintln!("{}: Error occurred: {}", label, err);
+          failed += 1;
+          continue;
+        }
+      };
+
+      match answers.check(*day, *part, &fingerprint, &answer) {
+        verify::Outcome::Match => println!("{}: ok", label),
+        verify::Outcome::Mismatch { expected } => {
+          println!("{}: MISMATCH\n  expected: {}\n       got: {}", label, expected, answer);
+          failed += 1;
+        },
+        verify::Outcome::Missing if options.record => {
+          println!("{}: recorded {}", label, answer);
+          answers.record(*day, *part, &fingerprint, &answer);
+          recorded += 1;
+        },
+        verify::Outcome::Missing => {
+          println!("{}: no answer recorded, got {}", label, answer);
+          missing += 1;
+        },
+      }
+    }
+  }
+
+  if recorded > 0 {
+    answers.save(answers_path)?;
+  }
+
+  if missing > 0 {
+    eprintln!("{} answer(s) are not recorded yet, rerun with --record to record them", missing);
+  }
+
+  if failed > 0 {
+    return Err(utils::Error::new(&format!("{} puzzle(s) failed verification", failed)));
+  }
+
+  Ok(())
+}
+
+fn watch(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let options = cli::watch_options(matches, latest_year(&registry)?)?;
+  let timeout = cli::timeout(matches)?;
+  let year = registry.year(options.year)
+    .ok_or(utils::Error::new(&format!("There are no solutions for {}", options.year)))?;
+  let entry = registry.get(options.year, options.day)
+    .ok_or(utils::Error::new(&format!("Day {} of {} is not solved yet", options.day, options.year)))?;
+
+  // the files are read from the sources, not the ones compiled into the binary
+  let dir = Path::new(year.src).join(format!("day{}", options.day)).join("data");
+  eprintln!("Watching {}, press Ctrl-C to stop", dir.display());
+
+  let mut session = watch::Session::new(entry.puzzle.as_ref(), &dir, &options.parts, timeout);
+  watch::watch(&mut session, options.interval, |line| println!("{}", line))
+}
+
+fn submit(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let options = cli::submit_options(matches, latest_year(&registry)?)?;
+
+  let answer = match options.answer {
+    Some(answer) => answer,
+    None => {
+      let entry = registry.get(options.year, options.day)
+        .ok_or(utils::Error::new(&format!("Day {} of {} is not solved yet", options.day, options.year)))?;
+      let input = options.input.read(options.year, options.day, entry.input)?;
+      let parsed = entry.puzzle.parse(&input)?;
+      match entry.puzzle.solve(options.part, parsed.as_ref())? {
+        Answer::None => return Err(utils::Error::new("There is no answer to submit for this part")),
+        Answer::Grid(_) => return Err(utils::Error::new("The answer has to be read off the grid, submit it with --answer")),
+        answer => answer.to_string(),
+      }
+    },
+  };
+
+  println!("Submitting {} for {}/{}/{}", answer, options.year, options.day, options.part);
+  let client = submit::Client::new(inputs::Config::from_env()?);
+  let verdict = client.submit(options.year, options.day, options.part, &answer)?;
+  println!("{}", verdict);
+
+  match verdict {
+    submit::Verdict::Correct => Ok(()),
+    _ => Err(utils::Error::new("The answer was not accepted")),
+  }
+}
+
+fn frames(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let options = cli::frames_options(matches, latest_year(&registry)?)?;
+  let entry = registry.get(options.year, options.day)
+    .ok_or(utils::Error::new(&format!("Day {} of {} is not solved yet", options.day, options.year)))?;
+  let sink = frames::from_spec(&options.sink, options.delay)?;
+
+  let input = options.input.read(options.year, options.day, entry.input)?;
+  let parsed = entry.puzzle.parse(&input)?;
+  let label = format!("{}/{}/{}", options.year, options.day, options.part);
+  let answer = frames::scope(&sink, &label, || entry.puzzle.solve(options.part, parsed.as_ref()))?;
+  println!("{}: {}", label, answer);
+
+  Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let addr = matches.value_of("addr").unwrap_or(aoc_core::serve::DEFAULT_ADDR);
+  let jobs = cli::jobs(matches)?;
+  let timeout = cli::timeout(matches)?;
+  let service = aoc_core::serve::Service::new(Arc::new(registry()), timeout);
+  aoc_core::serve::serve(service, addr, jobs)
+}
+
+fn new(matches: &ArgMatches) -> Result<(), utils::Error> {
+  let registry = registry();
+  let (year, day, src) = cli::new_options(matches, latest_year(&registry)?)?;
+  let src = match &src {
+    Some(src) => src.as_str(),
+    None => registry.year(year)
+      .ok_or(utils::Error::new(&format!("There is no crate for {} yet, pass its src directory with --src", year)))?
+      .src,
+  };
+
+  for file in scaffold::new_day(Path::new(src), day)? {
+    println!("Wrote {}", file.display());
+  }
+
+  Ok(())
+}
+
+fn main() {
+  let matches = cli::app().get_matches();
+
+  let result = match matches.subcommand() {
+    ("run", Some(m)) => run(m),
+    ("bench", Some(m)) => bench(m),
+    ("verify", Some(m)) => verify(m),
+    ("watch", Some(m)) => watch(m),
+    ("submit", Some(m)) => submit(m),
+    ("frames", Some(m)) => frames(m),
+    #[cfg(feature = "serve")]
+    ("serve", Some(m)) => serve(m),
+    ("new", Some(m)) => new(m),
+    _ => Ok(()),
+  };
+
+  if let Err(err) = result {
+    eprintln!("Error occurred: {}", err);
+    process::exit(1);
+  }
+}